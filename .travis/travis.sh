@@ -17,6 +17,7 @@ if [[ `git status --porcelain` ]]; then
 fi
 
 cargo test --all
+cargo test --all-features
 
 if [[ "$KOTLIN_TESTS" == "true" ]]; then
   cd platforms/kotlin
@@ -0,0 +1,16 @@
+fn main() {
+    #[cfg(feature = "protobuf")]
+    compile_protos();
+}
+
+/// Generates the Rust code of the protobuf messages, using the vendored `protoc` binary so that
+/// the build neither requires a system installation of `protoc` nor network access
+#[cfg(feature = "protobuf")]
+fn compile_protos() {
+    println!("cargo:rerun-if-changed=proto");
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("Cannot find vendored protoc");
+    prost_build::Config::new()
+        .protoc_executable(protoc)
+        .compile_protos(&["proto/snips_nlu_ontology.proto"], &["proto"])
+        .expect("Cannot compile protobuf definitions");
+}
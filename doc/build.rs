@@ -57,7 +57,12 @@ fn add_supported_builtin_entities(readme: &mut String) {
     readme.push_str("\n");
     let mut table = Table::new();
     table.set_format(*prettytable::format::consts::FORMAT_DEFAULT);
-    table.set_titles(row!["Entity", "Identifier", "Category"]);
+    table.set_titles(row![
+        "Entity",
+        "Identifier",
+        "Category",
+        "Supported Languages"
+    ]);
 
     let mut all_entities = BuiltinEntityKind::all().iter().collect::<Vec<_>>();
     all_entities.sort_by(|a, b| a.identifier().cmp(b.identifier()));
@@ -67,7 +72,18 @@ fn add_supported_builtin_entities(readme: &mut String) {
             .ok()
             .map(|_| "`Gazetteer Entity`_".to_string())
             .unwrap_or_else(|| "`Grammar Entity`_".to_string());
-        table.add_row(row![entity.to_string(), entity.identifier(), category]);
+        let supported_languages = entity
+            .supported_languages()
+            .iter()
+            .map(|language| format!("| {}", language.full_name()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        table.add_row(row![
+            entity.to_string(),
+            entity.identifier(),
+            category,
+            supported_languages
+        ]);
     }
     readme.push_str(&*table.to_string());
     readme.push_str("\n");
@@ -133,6 +149,20 @@ fn add_builtin_entity_results_examples(readme: &mut String, entity: BuiltinEntit
         .replace("--\n", "\n");
     readme.push_str(&*cleaned_title);
     readme.push_str("\n");
+    let mut examples_table = Table::new();
+    examples_table.set_format(*prettytable::format::consts::FORMAT_DEFAULT);
+    examples_table.set_titles(row!["Language", "Examples"]);
+    for language in entity.supported_languages() {
+        let examples = entity
+            .examples(*language)
+            .iter()
+            .map(|example| format!("| {}", example))
+            .collect::<Vec<_>>()
+            .join("\n");
+        examples_table.add_row(row![language.full_name(), examples]);
+    }
+    readme.push_str(&*examples_table.to_string());
+    readme.push_str("\n");
     readme.push_str(".. code-block:: json\n");
     readme.push_str("\n   ");
     readme.push_str(&*entity.result_description().replace("\n", "\n   "));
@@ -2,17 +2,19 @@
 
 use crate::errors::*;
 use crate::ontology::*;
+use failure::Fallible;
 use ffi_utils::take_back_c_string;
-use ffi_utils::{point_to_string, CStringArray, RawPointerConverter};
+use ffi_utils::{point_to_string, AsRust, CStringArray, RawPointerConverter};
 use lazy_static::lazy_static;
 use libc;
 use snips_nlu_ontology::{
-    BuiltinEntity, BuiltinEntityKind, BuiltinGazetteerEntityKind, GrammarEntityKind,
-    IntoBuiltinEntityKind,
+    convert_range, BuiltinEntity, BuiltinEntityKind, BuiltinGazetteerEntityKind, GrammarEntityKind,
+    IntoBuiltinEntityKind, Language, OffsetUnit, RANGE_OFFSET_UNIT,
 };
 use std::convert::From;
 use std::ffi::{CStr, CString};
 use std::slice;
+use std::str::FromStr;
 
 #[repr(C)]
 #[derive(Debug)]
@@ -22,6 +24,7 @@ pub struct CBuiltinEntity {
     pub value: *const libc::c_char,
     pub range_start: i32,
     pub range_end: i32,
+    pub offset_unit: SNIPS_OFFSET_UNIT,
 }
 
 impl From<BuiltinEntity> for CBuiltinEntity {
@@ -32,10 +35,56 @@ impl From<BuiltinEntity> for CBuiltinEntity {
             value: CString::new(e.value).unwrap().into_raw(),
             range_start: e.range.start as i32,
             range_end: e.range.end as i32,
+            offset_unit: SNIPS_OFFSET_UNIT::from(RANGE_OFFSET_UNIT),
         }
     }
 }
 
+impl CBuiltinEntity {
+    /// Builds an entity whose range in `input` is expressed in `offset_unit`
+    pub fn from_with_offset_unit(
+        e: BuiltinEntity,
+        input: &str,
+        offset_unit: OffsetUnit,
+    ) -> Result<Self> {
+        let range = e.range_in(input, offset_unit)?;
+        let mut c_entity = Self::from(e);
+        c_entity.range_start = range.start as i32;
+        c_entity.range_end = range.end as i32;
+        c_entity.offset_unit = SNIPS_OFFSET_UNIT::from(offset_unit);
+        Ok(c_entity)
+    }
+}
+
+/// Converts the range of `entity` in `input` to `offset_unit` and points `converted` to the new
+/// entity
+pub fn convert_builtin_entity_offset_unit(
+    input: *const libc::c_char,
+    entity: *const CBuiltinEntity,
+    offset_unit: SNIPS_OFFSET_UNIT,
+    converted: *mut *const CBuiltinEntity,
+) -> Fallible<()> {
+    let input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let entity = unsafe { &*entity };
+    let entity_kind_str = unsafe { CStr::from_ptr(entity.entity_kind) }.to_str()?;
+    let value = unsafe { CStr::from_ptr(entity.value) }.to_str()?;
+    let range = entity.range_start as usize..entity.range_end as usize;
+    let from = entity.offset_unit.as_rust()?;
+    let builtin_entity = BuiltinEntity {
+        value: value.to_string(),
+        range: convert_range(input, &range, from, RANGE_OFFSET_UNIT)?,
+        entity: entity.entity.as_rust()?,
+        alternatives: vec![],
+        entity_kind: BuiltinEntityKind::from_identifier(entity_kind_str)?,
+    };
+    let c_entity =
+        CBuiltinEntity::from_with_offset_unit(builtin_entity, input, offset_unit.as_rust()?)?;
+    unsafe {
+        *converted = c_entity.into_raw_pointer();
+    }
+    Ok(())
+}
+
 impl Drop for CBuiltinEntity {
     fn drop(&mut self) {
         take_back_c_string!(self.value);
@@ -140,8 +189,120 @@ pub fn all_gazetteer_entities() -> CStringArray {
 pub fn get_builtin_entity_shortname(
     entity_name: *const libc::c_char,
     result: *mut *const libc::c_char,
-) -> Result<()> {
+) -> Fallible<()> {
     let entity_str = unsafe { CStr::from_ptr(entity_name) }.to_str()?;
     let entity_kind = BuiltinEntityKind::from_identifier(entity_str)?;
     point_to_string(result, entity_kind.to_string())
 }
+
+pub fn get_supported_builtin_entities(
+    language: *const libc::c_char,
+    results: *mut *const CStringArray,
+) -> Fallible<()> {
+    let language_str = unsafe { CStr::from_ptr(language) }.to_str()?;
+    let language = Language::from_str(language_str)?;
+    let entities = language
+        .supported_builtin_entities()
+        .iter()
+        .map(|kind| kind.identifier().to_string())
+        .collect::<Vec<_>>();
+    let c_entities = CStringArray::from(entities).into_raw_pointer();
+    unsafe {
+        *results = c_entities;
+    }
+    Ok(())
+}
+
+pub fn get_builtin_entity_supported_languages(
+    entity_name: *const libc::c_char,
+    results: *mut *const CStringArray,
+) -> Fallible<()> {
+    let entity_str = unsafe { CStr::from_ptr(entity_name) }.to_str()?;
+    let entity_kind = BuiltinEntityKind::from_identifier(entity_str)?;
+    let languages = entity_kind
+        .supported_languages()
+        .iter()
+        .map(|language| language.to_string())
+        .collect::<Vec<_>>();
+    let c_languages = CStringArray::from(languages).into_raw_pointer();
+    unsafe {
+        *results = c_languages;
+    }
+    Ok(())
+}
+
+pub fn get_builtin_entity_examples(
+    entity_name: *const libc::c_char,
+    language: *const libc::c_char,
+    results: *mut *const CStringArray,
+) -> Fallible<()> {
+    let entity_str = unsafe { CStr::from_ptr(entity_name) }.to_str()?;
+    let entity_kind = BuiltinEntityKind::from_identifier(entity_str)?;
+    let language_str = unsafe { CStr::from_ptr(language) }.to_str()?;
+    let language = Language::from_str(language_str)?;
+    let examples = entity_kind
+        .examples(language)
+        .iter()
+        .map(|example| example.to_string())
+        .collect::<Vec<_>>();
+    let c_examples = CStringArray::from(examples).into_raw_pointer();
+    unsafe {
+        *results = c_examples;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snips_nlu_ontology::{NumberValue, SlotValue};
+    use std::ptr::null;
+
+    #[test]
+    fn convert_c_builtin_entity_offset_unit() {
+        // Given
+        let input = CString::new("𝄞 ten").unwrap();
+        let c_entity = CBuiltinEntity::from(BuiltinEntity {
+            value: "ten".to_string(),
+            range: 2..5,
+            entity: SlotValue::Number(NumberValue { value: 10. }),
+            alternatives: vec![],
+            entity_kind: BuiltinEntityKind::Number,
+        });
+        let mut converted: *const CBuiltinEntity = null();
+        let mut converted_back: *const CBuiltinEntity = null();
+
+        // When
+        convert_builtin_entity_offset_unit(
+            input.as_ptr(),
+            &c_entity,
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_UTF16,
+            &mut converted,
+        )
+        .unwrap();
+        convert_builtin_entity_offset_unit(
+            input.as_ptr(),
+            converted,
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_CHAR,
+            &mut converted_back,
+        )
+        .unwrap();
+
+        // Then
+        let converted = unsafe { CBuiltinEntity::from_raw_pointer(converted) }.unwrap();
+        let converted_back = unsafe { CBuiltinEntity::from_raw_pointer(converted_back) }.unwrap();
+        assert_eq!(
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_UTF16,
+            converted.offset_unit
+        );
+        assert_eq!((3, 6), (converted.range_start, converted.range_end));
+        assert_eq!(
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_CHAR,
+            converted_back.offset_unit
+        );
+        assert_eq!(
+            (2, 5),
+            (converted_back.range_start, converted_back.range_end)
+        );
+    }
+}
@@ -38,6 +38,101 @@ macro_rules! export_nlu_ontology_c_symbols {
             wrap!($crate::get_builtin_entity_shortname(entity_name, result))
         }
 
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_supported_builtin_entities(
+            language: *const libc::c_char,
+            results: *mut *const ::ffi_utils::CStringArray,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::get_supported_builtin_entities(language, results))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_builtin_entity_supported_languages(
+            entity_name: *const libc::c_char,
+            results: *mut *const ::ffi_utils::CStringArray,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::get_builtin_entity_supported_languages(
+                entity_name,
+                results
+            ))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_builtin_entity_examples(
+            entity_name: *const libc::c_char,
+            language: *const libc::c_char,
+            results: *mut *const ::ffi_utils::CStringArray,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::get_builtin_entity_examples(
+                entity_name,
+                language,
+                results
+            ))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_convert_offset(
+            text: *const libc::c_char,
+            offset: i32,
+            from: $crate::SNIPS_OFFSET_UNIT,
+            to: $crate::SNIPS_OFFSET_UNIT,
+            result: *mut i32,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::get_converted_offset(text, offset, from, to, result))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_convert_intent_parser_result_offset_unit(
+            result: *const $crate::CIntentParserResult,
+            offset_unit: $crate::SNIPS_OFFSET_UNIT,
+            converted: *mut *const $crate::CIntentParserResult,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::convert_intent_parser_result_offset_unit(
+                result,
+                offset_unit,
+                converted
+            ))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_convert_builtin_entity_offset_unit(
+            input: *const libc::c_char,
+            entity: *const $crate::CBuiltinEntity,
+            offset_unit: $crate::SNIPS_OFFSET_UNIT,
+            converted: *mut *const $crate::CBuiltinEntity,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::convert_builtin_entity_offset_unit(
+                input,
+                entity,
+                offset_unit,
+                converted
+            ))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_destroy_intent_parser_result(
+            ptr: *mut $crate::CIntentParserResult,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            use ffi_utils::RawPointerConverter;
+            wrap!(unsafe { $crate::CIntentParserResult::from_raw_pointer(ptr) })
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_destroy_builtin_entity(
+            ptr: *mut $crate::CBuiltinEntity,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            use ffi_utils::RawPointerConverter;
+            wrap!(unsafe { $crate::CBuiltinEntity::from_raw_pointer(ptr) })
+        }
+
+        #[no_mangle]
+        pub extern "C" fn snips_nlu_ontology_validate_intent_parser_result(
+            result: *const $crate::CIntentParserResult,
+            violations: *mut *const libc::c_char,
+        ) -> ::ffi_utils::SNIPS_RESULT {
+            wrap!($crate::validate_intent_parser_result(result, violations))
+        }
+
         #[no_mangle]
         pub extern "C" fn snips_nlu_ontology_all_builtin_entities() -> ::ffi_utils::CStringArray {
             $crate::all_builtin_entities()
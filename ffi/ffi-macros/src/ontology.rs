@@ -1,8 +1,8 @@
 #![allow(non_camel_case_types)]
 
-use failure::{bail, Fallible, ResultExt};
+use failure::Fallible;
 use ffi_utils::{
-    create_optional_rust_string_from, create_rust_string_from, take_back_c_string,
+    create_optional_rust_string_from, create_rust_string_from, point_to_string, take_back_c_string,
     take_back_nullable_c_string, AsRust, RawPointerConverter,
 };
 use libc;
@@ -23,6 +23,8 @@ pub struct CIntentParserResult {
     pub slots: *const CSlotList,
     /// Alternative parsings
     pub alternatives: *const CIntentParserAlternativeArray,
+    /// Unit in which the ranges of the slots are expressed
+    pub offset_unit: SNIPS_OFFSET_UNIT,
 }
 
 impl From<IntentParserResult> for CIntentParserResult {
@@ -33,21 +35,59 @@ impl From<IntentParserResult> for CIntentParserResult {
             slots: CSlotList::from(input.slots).into_raw_pointer(),
             alternatives: CIntentParserAlternativeArray::from(input.alternatives)
                 .into_raw_pointer(),
+            offset_unit: SNIPS_OFFSET_UNIT::from(RANGE_OFFSET_UNIT),
         }
     }
 }
 
+impl CIntentParserResult {
+    /// Builds a result whose slot ranges are expressed in `offset_unit`
+    pub fn from_with_offset_unit(
+        input: IntentParserResult,
+        offset_unit: OffsetUnit,
+    ) -> Fallible<Self> {
+        let converted = input.with_converted_ranges(RANGE_OFFSET_UNIT, offset_unit)?;
+        let mut c_result = Self::from(converted);
+        c_result.offset_unit = SNIPS_OFFSET_UNIT::from(offset_unit);
+        Ok(c_result)
+    }
+}
+
 impl AsRust<IntentParserResult> for CIntentParserResult {
     fn as_rust(&self) -> Fallible<IntentParserResult> {
-        Ok(IntentParserResult {
+        let result = IntentParserResult {
             input: create_rust_string_from!(self.input),
             intent: unsafe { &*self.intent }.as_rust()?,
             slots: unsafe { &*self.slots }.as_rust()?,
             alternatives: unsafe { &*self.alternatives }.as_rust()?,
-        })
+        };
+        Ok(result.with_converted_ranges(self.offset_unit.as_rust()?, RANGE_OFFSET_UNIT)?)
     }
 }
 
+/// Checks the consistency of the result and writes the violations found as a JSON array
+pub fn validate_intent_parser_result(
+    result: *const CIntentParserResult,
+    violations: *mut *const libc::c_char,
+) -> Fallible<()> {
+    let result = unsafe { &*result }.as_rust()?;
+    point_to_string(violations, serde_json::to_string(&result.validate())?)
+}
+
+/// Converts the slot ranges of `result` to `offset_unit` and points `converted` to the new result
+pub fn convert_intent_parser_result_offset_unit(
+    result: *const CIntentParserResult,
+    offset_unit: SNIPS_OFFSET_UNIT,
+    converted: *mut *const CIntentParserResult,
+) -> Fallible<()> {
+    let result = unsafe { &*result }.as_rust()?;
+    let c_result = CIntentParserResult::from_with_offset_unit(result, offset_unit.as_rust()?)?;
+    unsafe {
+        *converted = c_result.into_raw_pointer();
+    }
+    Ok(())
+}
+
 impl Drop for CIntentParserResult {
     fn drop(&mut self) {
         take_back_c_string!(self.input);
@@ -299,9 +339,10 @@ pub struct CSlot {
     pub entity: *const libc::c_char,
     /// Name of the slot
     pub slot_name: *const libc::c_char,
-    /// Start index of raw value in input text
+    /// Start index of raw value in input text, expressed in the offset unit of the enclosing
+    /// result
     pub range_start: i32,
-    /// End index of raw value in input text
+    /// End index of raw value in input text, expressed in the offset unit of the enclosing result
     pub range_end: i32,
     /// Confidence score of the slot
     pub confidence_score: libc::c_float,
@@ -353,6 +394,54 @@ impl Drop for CSlot {
     }
 }
 
+/// Enum representing the unit in which offsets in a text are expressed
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SNIPS_OFFSET_UNIT {
+    /// Offsets are expressed in bytes of the UTF-8 encoded text
+    SNIPS_OFFSET_UNIT_BYTE = 0,
+    /// Offsets are expressed in Unicode scalar values, this is the default unit
+    SNIPS_OFFSET_UNIT_CHAR = 1,
+    /// Offsets are expressed in UTF-16 code units, as in Java and Kotlin strings
+    SNIPS_OFFSET_UNIT_UTF16 = 2,
+}
+
+impl From<OffsetUnit> for SNIPS_OFFSET_UNIT {
+    fn from(value: OffsetUnit) -> Self {
+        match value {
+            OffsetUnit::Byte => SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_BYTE,
+            OffsetUnit::Char => SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_CHAR,
+            OffsetUnit::Utf16 => SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_UTF16,
+        }
+    }
+}
+
+impl AsRust<OffsetUnit> for SNIPS_OFFSET_UNIT {
+    fn as_rust(&self) -> Fallible<OffsetUnit> {
+        Ok(match self {
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_BYTE => OffsetUnit::Byte,
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_CHAR => OffsetUnit::Char,
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_UTF16 => OffsetUnit::Utf16,
+        })
+    }
+}
+
+pub fn get_converted_offset(
+    text: *const libc::c_char,
+    offset: i32,
+    from: SNIPS_OFFSET_UNIT,
+    to: SNIPS_OFFSET_UNIT,
+    result: *mut i32,
+) -> Fallible<()> {
+    let text = create_rust_string_from!(text);
+    let converted =
+        snips_nlu_ontology::convert_offset(&text, offset as usize, from.as_rust()?, to.as_rust()?)?;
+    unsafe {
+        *result = converted as i32;
+    }
+    Ok(())
+}
+
 /// Enum type describing how to cast the value of a CSlotValue
 #[repr(C)]
 #[derive(Debug, PartialEq)]
@@ -625,6 +714,46 @@ impl Drop for CAmountOfMoneyValue {
     }
 }
 
+/// Enum representing the unit of a temperature value
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub enum SNIPS_TEMPERATURE_UNIT {
+    /// The temperature is expressed in plain degrees, or in a unit which is not supported
+    SNIPS_TEMPERATURE_UNIT_DEGREE = 0,
+    /// The temperature is expressed in degrees Celsius
+    SNIPS_TEMPERATURE_UNIT_CELSIUS = 1,
+    /// The temperature is expressed in degrees Fahrenheit
+    SNIPS_TEMPERATURE_UNIT_FAHRENHEIT = 2,
+    /// The temperature is expressed in kelvins
+    SNIPS_TEMPERATURE_UNIT_KELVIN = 3,
+}
+
+impl From<TemperatureUnit> for SNIPS_TEMPERATURE_UNIT {
+    fn from(value: TemperatureUnit) -> Self {
+        match value {
+            TemperatureUnit::Degree => SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_DEGREE,
+            TemperatureUnit::Celsius => SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_CELSIUS,
+            TemperatureUnit::Fahrenheit => {
+                SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_FAHRENHEIT
+            }
+            TemperatureUnit::Kelvin => SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_KELVIN,
+        }
+    }
+}
+
+impl AsRust<TemperatureUnit> for SNIPS_TEMPERATURE_UNIT {
+    fn as_rust(&self) -> Fallible<TemperatureUnit> {
+        Ok(match self {
+            SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_DEGREE => TemperatureUnit::Degree,
+            SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_CELSIUS => TemperatureUnit::Celsius,
+            SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_FAHRENHEIT => {
+                TemperatureUnit::Fahrenheit
+            }
+            SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_KELVIN => TemperatureUnit::Kelvin,
+        })
+    }
+}
+
 /// Representation of a temperature value
 #[repr(C)]
 #[derive(Debug)]
@@ -633,12 +762,16 @@ pub struct CTemperatureValue {
     pub unit: *const libc::c_char,
     /// The temperature resolved
     pub value: libc::c_float,
+    /// The typed unit, parsed from the unit string
+    pub typed_unit: SNIPS_TEMPERATURE_UNIT,
 }
 
 impl From<TemperatureValue> for CTemperatureValue {
     fn from(value: TemperatureValue) -> Self {
+        let typed_unit = value.temperature_unit().unwrap_or(TemperatureUnit::Degree);
         Self {
             value: value.value as libc::c_float,
+            typed_unit: SNIPS_TEMPERATURE_UNIT::from(typed_unit),
             unit: if let Some(s) = value.unit {
                 CString::new(s).unwrap().into_raw()
             } else {
@@ -650,9 +783,11 @@ impl From<TemperatureValue> for CTemperatureValue {
 
 impl AsRust<TemperatureValue> for CTemperatureValue {
     fn as_rust(&self) -> Fallible<TemperatureValue> {
+        let typed_unit = self.typed_unit.as_rust()?;
         Ok(TemperatureValue {
             value: self.value as f32,
-            unit: create_optional_rust_string_from!(self.unit),
+            unit: create_optional_rust_string_from!(self.unit)
+                .or_else(|| typed_unit.identifier().map(|unit| unit.to_string())),
         })
     }
 }
@@ -817,10 +952,11 @@ impl AsRust<SlotValue> for CSlotValue {
             SNIPS_SLOT_VALUE_TYPE::SNIPS_SLOT_VALUE_TYPE_REGION => Ok(SlotValue::Region(
                 create_rust_string_from!(self.value as *const libc::c_char).into(),
             )),
-            _ => bail!(
+            _ => Err(OntologyError::ConversionFailure(format!(
                 "Unknown slot value type: {:?}. Cannot perform conversion to Rust object.",
                 self.value_type
-            ),
+            ))
+            .into()),
         }
     }
 }
@@ -967,6 +1103,36 @@ mod tests {
         })
     }
 
+    #[test]
+    fn round_trip_snips_temperature_unit() {
+        round_trip_test::<_, SNIPS_TEMPERATURE_UNIT>(TemperatureUnit::Kelvin)
+    }
+
+    #[test]
+    fn c_temperature_value_typed_unit() {
+        let c_value = CTemperatureValue::from(TemperatureValue {
+            value: 60.0,
+            unit: Some("fahrenheit".to_string()),
+        });
+        assert_eq!(
+            SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_FAHRENHEIT,
+            c_value.typed_unit
+        );
+
+        let c_value = CTemperatureValue {
+            unit: null(),
+            typed_unit: SNIPS_TEMPERATURE_UNIT::SNIPS_TEMPERATURE_UNIT_CELSIUS,
+            value: 23.0,
+        };
+        assert_eq!(
+            TemperatureValue {
+                value: 23.0,
+                unit: Some("celsius".to_string()),
+            },
+            c_value.as_rust().unwrap()
+        );
+    }
+
     #[test]
     fn round_trip_c_amount_of_value() {
         round_trip_test::<_, CAmountOfMoneyValue>(AmountOfMoneyValue {
@@ -1177,6 +1343,111 @@ mod tests {
         })
     }
 
+    #[test]
+    fn round_trip_snips_offset_unit() {
+        round_trip_test::<_, SNIPS_OFFSET_UNIT>(OffsetUnit::Utf16)
+    }
+
+    #[test]
+    fn c_intent_parser_result_with_offset_unit() {
+        // Given
+        let result = IntentParserResult {
+            input: "𝄞 music in 東京".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("playMusic".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![Slot::new_custom(
+                "東京".to_string(),
+                11..13,
+                "location".to_string(),
+                "city".to_string(),
+                None,
+                vec![],
+            )],
+            alternatives: vec![],
+        };
+
+        // When
+        let c_result =
+            CIntentParserResult::from_with_offset_unit(result.clone(), OffsetUnit::Utf16).unwrap();
+
+        // Then
+        let c_slot = unsafe { &*(*c_result.slots).slots };
+        assert_eq!(
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_UTF16,
+            c_result.offset_unit
+        );
+        assert_eq!((12, 14), (c_slot.range_start, c_slot.range_end));
+        assert_eq!(result, c_result.as_rust().unwrap());
+    }
+
+    #[test]
+    fn convert_c_intent_parser_result_offset_unit() {
+        // Given
+        let result = IntentParserResult {
+            input: "𝄞 music in 東京".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("playMusic".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![Slot::new_custom(
+                "東京".to_string(),
+                11..13,
+                "location".to_string(),
+                "city".to_string(),
+                None,
+                vec![],
+            )],
+            alternatives: vec![],
+        };
+        let c_result = CIntentParserResult::from(result.clone());
+        let mut converted: *const CIntentParserResult = null();
+
+        // When
+        convert_intent_parser_result_offset_unit(
+            &c_result,
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_BYTE,
+            &mut converted,
+        )
+        .unwrap();
+
+        // Then
+        let converted = unsafe { CIntentParserResult::from_raw_pointer(converted) }.unwrap();
+        let c_slot = unsafe { &*(*converted.slots).slots };
+        assert_eq!(
+            SNIPS_OFFSET_UNIT::SNIPS_OFFSET_UNIT_BYTE,
+            converted.offset_unit
+        );
+        assert_eq!((14, 20), (c_slot.range_start, c_slot.range_end));
+        assert_eq!(result, converted.as_rust().unwrap());
+    }
+
+    #[test]
+    fn validate_c_intent_parser_result() {
+        // Given
+        let c_result = CIntentParserResult::from(IntentParserResult {
+            input: "input".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("intent_name".to_string()),
+                confidence_score: 1.5,
+            },
+            slots: vec![],
+            alternatives: vec![],
+        });
+        let mut violations: *const libc::c_char = null();
+
+        // When
+        validate_intent_parser_result(&c_result, &mut violations).unwrap();
+
+        // Then
+        let violations = unsafe { CString::from_raw(violations as *mut libc::c_char) };
+        assert_eq!(
+            r#"[{"kind":"ConfidenceScoreOutOfBounds","path":"intent","confidenceScore":1.5}]"#,
+            violations.to_str().unwrap()
+        );
+    }
+
     #[test]
     fn round_trip_c_intent_parser_alternative() {
         round_trip_test::<_, CIntentParserAlternative>(IntentParserAlternative {
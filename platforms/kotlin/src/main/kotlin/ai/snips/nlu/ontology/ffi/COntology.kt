@@ -44,6 +44,7 @@ class CIntentParserResult(p: Pointer) : Structure(p), Structure.ByReference {
     @JvmField var intent: CIntentClassifierResult? = null
     @JvmField var slots: CSlots? = null
     @JvmField var alternatives: CIntentParserAlternativeArray? = null
+    @JvmField var offset_unit: Int? = null
 
     init {
         read()
@@ -52,7 +53,8 @@ class CIntentParserResult(p: Pointer) : Structure(p), Structure.ByReference {
     override fun getFieldOrder() = listOf("input",
                                           "intent",
                                           "slots",
-                                          "alternatives")
+                                          "alternatives",
+                                          "offset_unit")
 
     fun toIntentParserResult() = IntentParserResult(input = input.readString(),
                                                     intent = intent!!.toIntentClassifierResult(),
@@ -291,12 +293,14 @@ class CTemperatureValue(p: Pointer) : Structure(p), Structure.ByReference {
 
     @JvmField var value: Float? = null
     @JvmField var unit: Pointer? = null
+    // Only declared to match the C layout, TemperatureValue exposes the unit string
+    @JvmField var typed_unit: Int? = null
 
     init {
         read()
     }
 
-    override fun getFieldOrder() = listOf("unit", "value")
+    override fun getFieldOrder() = listOf("unit", "value", "typed_unit")
 
     fun toTemperatureValue() = TemperatureValue(value = value!!,
                                                 unit = unit?.readString())
@@ -0,0 +1,450 @@
+//! Normalization of the alternatives of intent parsing results
+//!
+//! Alternatives may come from several engines, in arbitrary order and with duplicates. The
+//! normalization removes the alternatives which are equivalent to the primary result or to a
+//! previous alternative, sorts intent alternatives by decreasing confidence score and only keeps
+//! the first ones.
+
+use crate::entity::custom_entity::normalize_entity_value;
+#[cfg(feature = "chrono")]
+use crate::ontology::parse_datetime;
+use crate::ontology::*;
+
+/// Maximum difference between two temperatures, once converted to the same unit, for them to be
+/// considered equal
+const TEMPERATURE_TOLERANCE: f32 = 1e-3;
+
+impl SlotValue {
+    /// Returns whether both values denote the same thing, regardless of their precision
+    ///
+    /// Strings are compared once normalized, datetimes as instants when the `chrono` feature is
+    /// enabled, amounts of money by currency, temperatures once converted to the same unit and
+    /// durations once normalized.
+    pub fn is_equivalent_to(&self, other: &SlotValue) -> bool {
+        match (self, other) {
+            (SlotValue::Custom(a), SlotValue::Custom(b))
+            | (SlotValue::MusicAlbum(a), SlotValue::MusicAlbum(b))
+            | (SlotValue::MusicArtist(a), SlotValue::MusicArtist(b))
+            | (SlotValue::MusicTrack(a), SlotValue::MusicTrack(b))
+            | (SlotValue::City(a), SlotValue::City(b))
+            | (SlotValue::Country(a), SlotValue::Country(b))
+            | (SlotValue::Region(a), SlotValue::Region(b)) => {
+                normalize_entity_value(&a.value) == normalize_entity_value(&b.value)
+            }
+            (SlotValue::Number(a), SlotValue::Number(b)) => a.value == b.value,
+            (SlotValue::Ordinal(a), SlotValue::Ordinal(b)) => a.value == b.value,
+            (SlotValue::Percentage(a), SlotValue::Percentage(b)) => a.value == b.value,
+            (SlotValue::InstantTime(a), SlotValue::InstantTime(b)) => {
+                a.grain == b.grain && datetimes_are_equivalent(&a.value, &b.value)
+            }
+            (SlotValue::TimeInterval(a), SlotValue::TimeInterval(b)) => {
+                bounds_are_equivalent(&a.from, &b.from) && bounds_are_equivalent(&a.to, &b.to)
+            }
+            (SlotValue::AmountOfMoney(a), SlotValue::AmountOfMoney(b)) => {
+                a.value == b.value
+                    && match (a.currency(), b.currency()) {
+                        (Some(a), Some(b)) => a.currency == b.currency,
+                        _ => a.unit == b.unit,
+                    }
+            }
+            (SlotValue::Temperature(a), SlotValue::Temperature(b)) => match a.temperature_unit() {
+                Ok(unit) => b
+                    .convert_to(unit)
+                    .map(|b| (a.value - b.value).abs() <= TEMPERATURE_TOLERANCE)
+                    .unwrap_or(false),
+                Err(_) => a == b,
+            },
+            (SlotValue::Duration(a), SlotValue::Duration(b)) => {
+                match (a.normalize(), b.normalize()) {
+                    (Ok(a), Ok(b)) => {
+                        DurationValue {
+                            precision: b.precision,
+                            ..a
+                        } == b
+                    }
+                    _ => a == b,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn datetimes_are_equivalent(a: &str, b: &str) -> bool {
+    match (parse_datetime(a), parse_datetime(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+fn datetimes_are_equivalent(a: &str, b: &str) -> bool {
+    a == b
+}
+
+fn bounds_are_equivalent(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => datetimes_are_equivalent(a, b),
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+fn slots_are_equivalent(slots: &[Slot], other_slots: &[Slot]) -> bool {
+    slots.len() == other_slots.len()
+        && slots.iter().all(|slot| {
+            other_slots.iter().any(|other_slot| {
+                slot.range == other_slot.range
+                    && slot.slot_name == other_slot.slot_name
+                    && slot.entity == other_slot.entity
+                    && slot.value.is_equivalent_to(&other_slot.value)
+            })
+        })
+}
+
+fn is_equivalent_alternative(
+    alternative: &IntentParserAlternative,
+    other: &IntentParserAlternative,
+) -> bool {
+    alternative.intent.intent_name == other.intent.intent_name
+        && slots_are_equivalent(&alternative.slots, &other.slots)
+}
+
+impl Slot {
+    /// Returns a copy of the slot whose alternative values are neither equivalent to its value
+    /// nor to a previous alternative, keeping at most `max_alternatives` of them
+    pub fn normalize_alternatives(&self, max_alternatives: Option<usize>) -> Slot {
+        let mut alternatives: Vec<SlotValue> = vec![];
+        for alternative in &self.alternatives {
+            if !alternative.is_equivalent_to(&self.value)
+                && !alternatives
+                    .iter()
+                    .any(|previous| previous.is_equivalent_to(alternative))
+            {
+                alternatives.push(alternative.clone())
+            }
+        }
+        if let Some(max_alternatives) = max_alternatives {
+            alternatives.truncate(max_alternatives);
+        }
+        Slot {
+            alternatives,
+            ..self.clone()
+        }
+    }
+}
+
+impl IntentParserResult {
+    /// Returns a copy of the result whose alternatives are normalized
+    ///
+    /// Intent alternatives which are equivalent to the primary result or to a more confident
+    /// alternative, i.e. with the same intent and equivalent slots, are removed. The remaining
+    /// ones are sorted by decreasing confidence score. The alternative values of all the slots
+    /// are normalized as well, and `max_alternatives` applies to both kinds of alternatives.
+    pub fn normalize_alternatives(&self, max_alternatives: Option<usize>) -> IntentParserResult {
+        let normalize_slots = |slots: &[Slot]| {
+            slots
+                .iter()
+                .map(|slot| slot.normalize_alternatives(max_alternatives))
+                .collect::<Vec<_>>()
+        };
+        let primary = IntentParserAlternative {
+            intent: self.intent.clone(),
+            slots: self.slots.clone(),
+        };
+        let mut sorted_alternatives = self.alternatives.iter().collect::<Vec<_>>();
+        sorted_alternatives.sort_by(|a, b| {
+            cmp_confidence_scores(b.intent.confidence_score, a.intent.confidence_score)
+        });
+        let mut alternatives: Vec<IntentParserAlternative> = vec![];
+        for alternative in sorted_alternatives {
+            if !is_equivalent_alternative(alternative, &primary)
+                && !alternatives
+                    .iter()
+                    .any(|previous| is_equivalent_alternative(previous, alternative))
+            {
+                alternatives.push(IntentParserAlternative {
+                    intent: alternative.intent.clone(),
+                    slots: normalize_slots(&alternative.slots),
+                })
+            }
+        }
+        if let Some(max_alternatives) = max_alternatives {
+            alternatives.truncate(max_alternatives);
+        }
+        IntentParserResult {
+            input: self.input.clone(),
+            intent: self.intent.clone(),
+            slots: normalize_slots(&self.slots),
+            alternatives,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{intent, slot};
+
+    fn string_value(value: &str) -> StringValue {
+        value.to_string().into()
+    }
+
+    fn instant_time(value: &str, grain: Grain) -> SlotValue {
+        SlotValue::InstantTime(InstantTimeValue {
+            value: value.to_string(),
+            grain,
+            precision: Precision::Exact,
+        })
+    }
+
+    fn time_interval(from: Option<&str>, to: Option<&str>) -> SlotValue {
+        SlotValue::TimeInterval(TimeIntervalValue {
+            from: from.map(|from| from.to_string()),
+            to: to.map(|to| to.to_string()),
+        })
+    }
+
+    fn amount_of_money(value: f32, precision: Precision, unit: &str) -> SlotValue {
+        SlotValue::AmountOfMoney(AmountOfMoneyValue {
+            value,
+            precision,
+            unit: Some(unit.to_string()),
+        })
+    }
+
+    fn temperature(value: f32, unit: Option<&str>) -> SlotValue {
+        SlotValue::Temperature(TemperatureValue {
+            value,
+            unit: unit.map(|unit| unit.to_string()),
+        })
+    }
+
+    fn duration(hours: i64, minutes: i64, precision: Precision) -> SlotValue {
+        SlotValue::Duration(DurationValue {
+            hours,
+            minutes,
+            precision,
+            ..DurationValue::zero()
+        })
+    }
+
+    /// Values along with an equivalent value and a different one
+    fn values() -> Vec<(SlotValue, SlotValue, SlotValue)> {
+        vec![
+            (
+                SlotValue::Custom(string_value("Café au lait")),
+                SlotValue::Custom(string_value(" café  AU LAIT")),
+                SlotValue::Custom(string_value("latte")),
+            ),
+            (
+                SlotValue::Number(NumberValue { value: 2. }),
+                SlotValue::Number(NumberValue { value: 2.0 }),
+                SlotValue::Number(NumberValue { value: 2.5 }),
+            ),
+            (
+                SlotValue::Ordinal(OrdinalValue { value: 2 }),
+                SlotValue::Ordinal(OrdinalValue { value: 2 }),
+                SlotValue::Ordinal(OrdinalValue { value: 3 }),
+            ),
+            (
+                SlotValue::Percentage(PercentageValue { value: 20. }),
+                SlotValue::Percentage(PercentageValue { value: 20. }),
+                SlotValue::Percentage(PercentageValue { value: 0.2 }),
+            ),
+            (
+                instant_time("2017-06-13 18:00:00 +02:00", Grain::Hour),
+                instant_time("2017-06-13 18:00:00 +02:00", Grain::Hour),
+                instant_time("2017-06-13 18:00:00 +02:00", Grain::Day),
+            ),
+            (
+                time_interval(Some("2017-06-13 18:00:00 +02:00"), None),
+                time_interval(Some("2017-06-13 18:00:00 +02:00"), None),
+                time_interval(
+                    Some("2017-06-13 18:00:00 +02:00"),
+                    Some("2017-06-13 20:00:00 +02:00"),
+                ),
+            ),
+            (
+                amount_of_money(10., Precision::Exact, "€"),
+                amount_of_money(10., Precision::Approximate, "EUR"),
+                amount_of_money(10., Precision::Exact, "USD"),
+            ),
+            (
+                temperature(20., Some("celsius")),
+                temperature(68., Some("°F")),
+                temperature(20., None),
+            ),
+            (
+                duration(0, 90, Precision::Exact),
+                duration(1, 30, Precision::Approximate),
+                duration(1, 0, Precision::Exact),
+            ),
+            (
+                SlotValue::MusicAlbum(string_value("Thriller")),
+                SlotValue::MusicAlbum(string_value("thriller")),
+                SlotValue::MusicTrack(string_value("Thriller")),
+            ),
+            (
+                SlotValue::MusicArtist(string_value("Daft Punk")),
+                SlotValue::MusicArtist(string_value("daft punk")),
+                SlotValue::MusicArtist(string_value("Justice")),
+            ),
+            (
+                SlotValue::MusicTrack(string_value("Get Lucky")),
+                SlotValue::MusicTrack(string_value("get  lucky")),
+                SlotValue::MusicTrack(string_value("Lucky")),
+            ),
+            (
+                SlotValue::City(string_value("Paris")),
+                SlotValue::City(string_value("PARIS")),
+                SlotValue::Region(string_value("Paris")),
+            ),
+            (
+                SlotValue::Country(string_value("France")),
+                SlotValue::Country(string_value("france")),
+                SlotValue::Country(string_value("Germany")),
+            ),
+            (
+                SlotValue::Region(string_value("Brittany")),
+                SlotValue::Region(string_value("brittany")),
+                SlotValue::Region(string_value("Normandy")),
+            ),
+        ]
+    }
+
+    fn alternative(
+        intent_name: Option<&str>,
+        confidence_score: f32,
+        slots: Vec<Slot>,
+    ) -> IntentParserAlternative {
+        IntentParserAlternative {
+            intent: intent(intent_name, confidence_score),
+            slots,
+        }
+    }
+
+    #[test]
+    fn test_slot_value_equivalence() {
+        for (value, equivalent_value, different_value) in values() {
+            assert!(
+                value.is_equivalent_to(&equivalent_value),
+                "{:?} should be equivalent to {:?}",
+                value,
+                equivalent_value
+            );
+            assert!(
+                !value.is_equivalent_to(&different_value),
+                "{:?} should not be equivalent to {:?}",
+                value,
+                different_value
+            );
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_datetimes_are_compared_as_instants() {
+        // Given
+        let value = instant_time("2017-06-13 18:00:00 +02:00", Grain::Hour);
+        let equivalent_value = instant_time("2017-06-13 16:00:00 +00:00", Grain::Hour);
+
+        // When/Then
+        assert!(value.is_equivalent_to(&equivalent_value));
+    }
+
+    #[test]
+    fn test_normalize_slot_alternatives() {
+        for (value, equivalent_value, different_value) in values() {
+            // Given
+            let slot = Slot {
+                value: value.clone(),
+                alternatives: vec![
+                    equivalent_value.clone(),
+                    different_value.clone(),
+                    different_value.clone(),
+                ],
+                ..slot("slot", "entity", 0..5)
+            };
+
+            // When
+            let normalized_slot = slot.normalize_alternatives(None);
+
+            // Then
+            assert_eq!(vec![different_value], normalized_slot.alternatives);
+        }
+    }
+
+    #[test]
+    fn test_normalize_alternatives() {
+        // Given
+        let number = |value| SlotValue::Number(NumberValue { value });
+        let number_slot = |value, alternatives| Slot {
+            value: number(value),
+            alternatives,
+            ..slot("slot", "entity", 0..5)
+        };
+        let result = IntentParserResult {
+            input: "value".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("makeCoffee".to_string()),
+                confidence_score: 0.7,
+            },
+            slots: vec![number_slot(2., vec![number(2.), number(3.), number(4.)])],
+            alternatives: vec![
+                alternative(Some("makeTea"), 0.1, vec![]),
+                alternative(Some("makeCoffee"), 0.6, vec![number_slot(2., vec![])]),
+                alternative(None, 0.2, vec![]),
+                alternative(Some("makeCoffee"), 0.5, vec![number_slot(3., vec![])]),
+                alternative(None, 0.15, vec![]),
+            ],
+        };
+
+        // When
+        let normalized_result = result.normalize_alternatives(Some(2));
+
+        // Then
+        let expected_result = IntentParserResult {
+            input: "value".to_string(),
+            intent: result.intent.clone(),
+            slots: vec![number_slot(2., vec![number(3.), number(4.)])],
+            alternatives: vec![
+                alternative(Some("makeCoffee"), 0.5, vec![number_slot(3., vec![])]),
+                alternative(None, 0.2, vec![]),
+            ],
+        };
+        assert_eq!(expected_result, normalized_result);
+    }
+
+    #[test]
+    fn test_nan_confidence_scores_are_sorted_last() {
+        // Given
+        let result = IntentParserResult {
+            input: "value".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("makeCoffee".to_string()),
+                confidence_score: 0.7,
+            },
+            slots: vec![],
+            alternatives: vec![
+                alternative(Some("makeTea"), f32::NAN, vec![]),
+                alternative(None, 0.2, vec![]),
+                alternative(Some("makeChocolate"), 0.3, vec![]),
+            ],
+        };
+
+        // When
+        let normalized_result = result.normalize_alternatives(None);
+
+        // Then
+        let intent_names = normalized_result
+            .alternatives
+            .iter()
+            .map(|alternative| alternative.intent.intent_name.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Some("makeChocolate"), None, Some("makeTea")],
+            intent_names
+        );
+    }
+}
@@ -0,0 +1,421 @@
+//! Binary encodings of the ontology types, available with the `cbor`, `messagepack` and `bincode`
+//! features
+//!
+//! Each encoding is enabled by its own feature:
+//!
+//! * `cbor`: CBOR, as defined in RFC 8949
+//! * `messagepack`: MessagePack, with structs encoded as maps
+//! * `bincode`: bincode, the most compact of the three
+//!
+//! CBOR and MessagePack are self-describing formats, hence they share the representation used
+//! for JSON. This is not the case of bincode, which cannot decode the internally tagged
+//! `SlotValue` nor the fields skipped when serializing, so values are converted to an equivalent
+//! representation using an externally tagged enum before being encoded with bincode.
+
+#[cfg(any(feature = "cbor", feature = "messagepack"))]
+use crate::errors::*;
+#[cfg(any(feature = "cbor", feature = "messagepack"))]
+use serde::de::DeserializeOwned;
+#[cfg(any(feature = "cbor", feature = "messagepack"))]
+use serde::Serialize;
+
+#[cfg(feature = "cbor")]
+pub fn to_cbor<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let mut bytes = vec![];
+    ciborium::ser::into_writer(value, &mut bytes).map_err(OntologyError::conversion_failure)?;
+    Ok(bytes)
+}
+
+#[cfg(feature = "cbor")]
+pub fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    ciborium::de::from_reader(bytes).map_err(OntologyError::conversion_failure)
+}
+
+#[cfg(feature = "messagepack")]
+pub fn to_messagepack<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(value).map_err(OntologyError::conversion_failure)
+}
+
+#[cfg(feature = "messagepack")]
+pub fn from_messagepack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    rmp_serde::from_slice(bytes).map_err(OntologyError::conversion_failure)
+}
+
+#[cfg(feature = "bincode")]
+pub use self::bincode_encoding::BincodeEncoding;
+
+#[cfg(feature = "bincode")]
+mod bincode_encoding {
+    use crate::entity::builtin_entity::{BuiltinEntity, BuiltinEntityKind};
+    use crate::errors::*;
+    use crate::ontology::*;
+    use std::ops::Range;
+
+    /// Types which can be encoded with bincode
+    pub trait BincodeEncoding: Sized {
+        fn to_bincode(&self) -> Result<Vec<u8>>;
+        fn from_bincode(bytes: &[u8]) -> Result<Self>;
+    }
+
+    /// Implements `BincodeEncoding` for types whose serde representation is supported by bincode
+    macro_rules! impl_direct_bincode_encoding {
+        ($($type:ty),*) => {
+            $(
+                impl BincodeEncoding for $type {
+                    fn to_bincode(&self) -> Result<Vec<u8>> {
+                        bincode::serialize(self).map_err(OntologyError::conversion_failure)
+                    }
+
+                    fn from_bincode(bytes: &[u8]) -> Result<Self> {
+                        bincode::deserialize(bytes).map_err(OntologyError::conversion_failure)
+                    }
+                }
+            )*
+        };
+    }
+
+    /// Implements `BincodeEncoding` for types which are encoded through a compact counterpart
+    macro_rules! impl_compact_bincode_encoding {
+        ($($type:ty => $compact:ty),*) => {
+            $(
+                impl BincodeEncoding for $type {
+                    fn to_bincode(&self) -> Result<Vec<u8>> {
+                        bincode::serialize(&<$compact>::from(self.clone()))
+                            .map_err(OntologyError::conversion_failure)
+                    }
+
+                    fn from_bincode(bytes: &[u8]) -> Result<Self> {
+                        bincode::deserialize::<$compact>(bytes)
+                            .map(Into::into)
+                            .map_err(OntologyError::conversion_failure)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_direct_bincode_encoding!(
+        IntentClassifierResult,
+        StringValue,
+        NumberValue,
+        OrdinalValue,
+        PercentageValue,
+        InstantTimeValue,
+        TimeIntervalValue,
+        AmountOfMoneyValue,
+        TemperatureValue,
+        DurationValue,
+        Grain,
+        Precision
+    );
+
+    impl_compact_bincode_encoding!(
+        IntentParserResult => CompactIntentParserResult,
+        IntentParserAlternative => CompactIntentParserAlternative,
+        Slot => CompactSlot,
+        SlotValue => CompactSlotValue,
+        BuiltinEntity => CompactBuiltinEntity
+    );
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactIntentParserResult {
+        input: String,
+        intent: IntentClassifierResult,
+        slots: Vec<CompactSlot>,
+        alternatives: Vec<CompactIntentParserAlternative>,
+    }
+
+    impl From<IntentParserResult> for CompactIntentParserResult {
+        fn from(result: IntentParserResult) -> Self {
+            Self {
+                input: result.input,
+                intent: result.intent,
+                slots: result.slots.into_iter().map(CompactSlot::from).collect(),
+                alternatives: result
+                    .alternatives
+                    .into_iter()
+                    .map(CompactIntentParserAlternative::from)
+                    .collect(),
+            }
+        }
+    }
+
+    impl From<CompactIntentParserResult> for IntentParserResult {
+        fn from(result: CompactIntentParserResult) -> Self {
+            Self {
+                input: result.input,
+                intent: result.intent,
+                slots: result.slots.into_iter().map(Slot::from).collect(),
+                alternatives: result
+                    .alternatives
+                    .into_iter()
+                    .map(IntentParserAlternative::from)
+                    .collect(),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactIntentParserAlternative {
+        intent: IntentClassifierResult,
+        slots: Vec<CompactSlot>,
+    }
+
+    impl From<IntentParserAlternative> for CompactIntentParserAlternative {
+        fn from(alternative: IntentParserAlternative) -> Self {
+            Self {
+                intent: alternative.intent,
+                slots: alternative
+                    .slots
+                    .into_iter()
+                    .map(CompactSlot::from)
+                    .collect(),
+            }
+        }
+    }
+
+    impl From<CompactIntentParserAlternative> for IntentParserAlternative {
+        fn from(alternative: CompactIntentParserAlternative) -> Self {
+            Self {
+                intent: alternative.intent,
+                slots: alternative.slots.into_iter().map(Slot::from).collect(),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactSlot {
+        raw_value: String,
+        value: CompactSlotValue,
+        alternatives: Vec<CompactSlotValue>,
+        range: Range<usize>,
+        entity: String,
+        slot_name: String,
+        confidence_score: Option<f32>,
+    }
+
+    impl From<Slot> for CompactSlot {
+        fn from(slot: Slot) -> Self {
+            Self {
+                raw_value: slot.raw_value,
+                value: slot.value.into(),
+                alternatives: slot.alternatives.into_iter().map(Into::into).collect(),
+                range: slot.range,
+                entity: slot.entity,
+                slot_name: slot.slot_name,
+                confidence_score: slot.confidence_score,
+            }
+        }
+    }
+
+    impl From<CompactSlot> for Slot {
+        fn from(slot: CompactSlot) -> Self {
+            Self {
+                raw_value: slot.raw_value,
+                value: slot.value.into(),
+                alternatives: slot.alternatives.into_iter().map(Into::into).collect(),
+                range: slot.range,
+                entity: slot.entity,
+                slot_name: slot.slot_name,
+                confidence_score: slot.confidence_score,
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum CompactSlotValue {
+        Custom(StringValue),
+        Number(NumberValue),
+        Ordinal(OrdinalValue),
+        Percentage(PercentageValue),
+        InstantTime(InstantTimeValue),
+        TimeInterval(TimeIntervalValue),
+        AmountOfMoney(AmountOfMoneyValue),
+        Temperature(TemperatureValue),
+        Duration(DurationValue),
+        MusicAlbum(StringValue),
+        MusicArtist(StringValue),
+        MusicTrack(StringValue),
+        City(StringValue),
+        Country(StringValue),
+        Region(StringValue),
+    }
+
+    impl From<SlotValue> for CompactSlotValue {
+        fn from(value: SlotValue) -> Self {
+            match value {
+                SlotValue::Custom(v) => CompactSlotValue::Custom(v),
+                SlotValue::Number(v) => CompactSlotValue::Number(v),
+                SlotValue::Ordinal(v) => CompactSlotValue::Ordinal(v),
+                SlotValue::Percentage(v) => CompactSlotValue::Percentage(v),
+                SlotValue::InstantTime(v) => CompactSlotValue::InstantTime(v),
+                SlotValue::TimeInterval(v) => CompactSlotValue::TimeInterval(v),
+                SlotValue::AmountOfMoney(v) => CompactSlotValue::AmountOfMoney(v),
+                SlotValue::Temperature(v) => CompactSlotValue::Temperature(v),
+                SlotValue::Duration(v) => CompactSlotValue::Duration(v),
+                SlotValue::MusicAlbum(v) => CompactSlotValue::MusicAlbum(v),
+                SlotValue::MusicArtist(v) => CompactSlotValue::MusicArtist(v),
+                SlotValue::MusicTrack(v) => CompactSlotValue::MusicTrack(v),
+                SlotValue::City(v) => CompactSlotValue::City(v),
+                SlotValue::Country(v) => CompactSlotValue::Country(v),
+                SlotValue::Region(v) => CompactSlotValue::Region(v),
+            }
+        }
+    }
+
+    impl From<CompactSlotValue> for SlotValue {
+        fn from(value: CompactSlotValue) -> Self {
+            match value {
+                CompactSlotValue::Custom(v) => SlotValue::Custom(v),
+                CompactSlotValue::Number(v) => SlotValue::Number(v),
+                CompactSlotValue::Ordinal(v) => SlotValue::Ordinal(v),
+                CompactSlotValue::Percentage(v) => SlotValue::Percentage(v),
+                CompactSlotValue::InstantTime(v) => SlotValue::InstantTime(v),
+                CompactSlotValue::TimeInterval(v) => SlotValue::TimeInterval(v),
+                CompactSlotValue::AmountOfMoney(v) => SlotValue::AmountOfMoney(v),
+                CompactSlotValue::Temperature(v) => SlotValue::Temperature(v),
+                CompactSlotValue::Duration(v) => SlotValue::Duration(v),
+                CompactSlotValue::MusicAlbum(v) => SlotValue::MusicAlbum(v),
+                CompactSlotValue::MusicArtist(v) => SlotValue::MusicArtist(v),
+                CompactSlotValue::MusicTrack(v) => SlotValue::MusicTrack(v),
+                CompactSlotValue::City(v) => SlotValue::City(v),
+                CompactSlotValue::Country(v) => SlotValue::Country(v),
+                CompactSlotValue::Region(v) => SlotValue::Region(v),
+            }
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactBuiltinEntity {
+        value: String,
+        range: Range<usize>,
+        entity: CompactSlotValue,
+        alternatives: Vec<CompactSlotValue>,
+        entity_kind: BuiltinEntityKind,
+    }
+
+    impl From<BuiltinEntity> for CompactBuiltinEntity {
+        fn from(entity: BuiltinEntity) -> Self {
+            Self {
+                value: entity.value,
+                range: entity.range,
+                entity: entity.entity.into(),
+                alternatives: entity.alternatives.into_iter().map(Into::into).collect(),
+                entity_kind: entity.entity_kind,
+            }
+        }
+    }
+
+    impl From<CompactBuiltinEntity> for BuiltinEntity {
+        fn from(entity: CompactBuiltinEntity) -> Self {
+            Self {
+                value: entity.value,
+                range: entity.range,
+                entity: entity.entity.into(),
+                alternatives: entity.alternatives.into_iter().map(Into::into).collect(),
+                entity_kind: entity.entity_kind,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::builtin_entity::{BuiltinEntity, BuiltinEntityKind};
+    use crate::ontology::*;
+    use crate::test_utils::all_slot_values;
+
+    fn intent_parser_result() -> IntentParserResult {
+        let slots = all_slot_values()
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| Slot {
+                raw_value: "value".to_string(),
+                value,
+                alternatives: vec![SlotValue::Custom("alternative".into())],
+                range: 0..5,
+                entity: "entity".to_string(),
+                slot_name: "slot_name".to_string(),
+                confidence_score: if index % 2 == 0 { Some(0.5) } else { None },
+            })
+            .collect::<Vec<_>>();
+        IntentParserResult {
+            input: "value".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("intent_name".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: slots.clone(),
+            alternatives: vec![IntentParserAlternative {
+                intent: IntentClassifierResult {
+                    intent_name: None,
+                    confidence_score: 0.2,
+                },
+                slots,
+            }],
+        }
+    }
+
+    fn builtin_entity() -> BuiltinEntity {
+        BuiltinEntity {
+            value: "tomorrow".to_string(),
+            range: 3..11,
+            entity: all_slot_values()[4].clone(),
+            alternatives: vec![all_slot_values()[5].clone()],
+            entity_kind: BuiltinEntityKind::Datetime,
+        }
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_cbor_round_trip() {
+        for value in all_slot_values() {
+            let bytes = to_cbor(&value).unwrap();
+            assert_eq!(value, from_cbor::<SlotValue>(&bytes).unwrap());
+        }
+        let result = intent_parser_result();
+        let bytes = to_cbor(&result).unwrap();
+        assert_eq!(result, from_cbor::<IntentParserResult>(&bytes).unwrap());
+        let entity = builtin_entity();
+        let bytes = to_cbor(&entity).unwrap();
+        assert_eq!(entity, from_cbor::<BuiltinEntity>(&bytes).unwrap());
+    }
+
+    #[cfg(feature = "messagepack")]
+    #[test]
+    fn test_messagepack_round_trip() {
+        for value in all_slot_values() {
+            let bytes = to_messagepack(&value).unwrap();
+            assert_eq!(value, from_messagepack::<SlotValue>(&bytes).unwrap());
+        }
+        let result = intent_parser_result();
+        let bytes = to_messagepack(&result).unwrap();
+        assert_eq!(
+            result,
+            from_messagepack::<IntentParserResult>(&bytes).unwrap()
+        );
+        let entity = builtin_entity();
+        let bytes = to_messagepack(&entity).unwrap();
+        assert_eq!(entity, from_messagepack::<BuiltinEntity>(&bytes).unwrap());
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_bincode_round_trip() {
+        for value in all_slot_values() {
+            let bytes = value.to_bincode().unwrap();
+            assert_eq!(value, SlotValue::from_bincode(&bytes).unwrap());
+        }
+        let result = intent_parser_result();
+        let bytes = result.to_bincode().unwrap();
+        assert_eq!(result, IntentParserResult::from_bincode(&bytes).unwrap());
+        assert!(bytes.len() < serde_json::to_vec(&result).unwrap().len());
+        let entity = builtin_entity();
+        let bytes = entity.to_bincode().unwrap();
+        assert_eq!(entity, BuiltinEntity::from_bincode(&bytes).unwrap());
+        let slot = result.slots[1].clone();
+        let bytes = slot.to_bincode().unwrap();
+        assert_eq!(slot, Slot::from_bincode(&bytes).unwrap());
+    }
+}
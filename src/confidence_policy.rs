@@ -0,0 +1,452 @@
+//! Post-processing of intent parsing results based on their confidence scores
+//!
+//! A `ConfidencePolicy` is a list of rules, applied in order to a copy of the result, e.g.:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     {"kind": "MinIntentConfidence", "threshold": 0.4, "promotableIntents": ["makeTea"]},
+//!     {"kind": "MinSlotConfidence", "threshold": 0.2}
+//!   ]
+//! }
+//! ```
+//!
+//! Each change made by a rule is recorded in the trace returned along with the new result.
+
+use crate::ontology::{IntentClassifierResult, IntentParserAlternative, IntentParserResult, Slot};
+use std::mem;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum PolicyRule {
+    /// Demotes the intent when its confidence score is below the threshold
+    ///
+    /// The first alternative of one of the promotable intents replaces the demoted intent. When
+    /// there is none, the null intent is used instead, taken from the alternatives if found there
+    /// and otherwise with the complementary confidence score. The demoted intent and its slots
+    /// become an alternative, ranked by confidence score among the others.
+    #[serde(rename_all = "camelCase")]
+    MinIntentConfidence {
+        threshold: f32,
+        #[serde(default)]
+        promotable_intents: Vec<String>,
+    },
+    /// Drops the slots, of the result and of its alternatives, whose confidence score is below
+    /// the threshold, slots without confidence score being kept
+    MinSlotConfidence { threshold: f32 },
+}
+
+/// Change made by a rule, which is referred to by its index in the policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum PolicyEvent {
+    #[serde(rename_all = "camelCase")]
+    IntentDemoted {
+        rule_index: usize,
+        intent_name: String,
+        confidence_score: f32,
+    },
+    /// An alternative, possibly of the null intent, has replaced a demoted intent
+    #[serde(rename_all = "camelCase")]
+    AlternativePromoted {
+        rule_index: usize,
+        intent_name: Option<String>,
+        confidence_score: f32,
+    },
+    #[serde(rename_all = "camelCase")]
+    SlotDropped {
+        rule_index: usize,
+        /// Path of the slot before the rule is applied, e.g. `alternatives[0].slots[1]`
+        path: String,
+        slot_name: String,
+        range: Range<usize>,
+        confidence_score: f32,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidencePolicy {
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Result of the application of a policy, along with the changes made by its rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyOutcome {
+    pub result: IntentParserResult,
+    pub trace: Vec<PolicyEvent>,
+}
+
+impl ConfidencePolicy {
+    pub fn apply(&self, result: &IntentParserResult) -> PolicyOutcome {
+        let mut result = result.clone();
+        let mut trace = vec![];
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            match rule {
+                PolicyRule::MinIntentConfidence {
+                    threshold,
+                    promotable_intents,
+                } => apply_min_intent_confidence(
+                    rule_index,
+                    *threshold,
+                    promotable_intents,
+                    &mut result,
+                    &mut trace,
+                ),
+                PolicyRule::MinSlotConfidence { threshold } => {
+                    apply_min_slot_confidence(rule_index, *threshold, &mut result, &mut trace)
+                }
+            }
+        }
+        PolicyOutcome { result, trace }
+    }
+}
+
+fn apply_min_intent_confidence(
+    rule_index: usize,
+    threshold: f32,
+    promotable_intents: &[String],
+    result: &mut IntentParserResult,
+    trace: &mut Vec<PolicyEvent>,
+) {
+    let intent_name = match result.intent.intent_name {
+        Some(ref intent_name) if result.intent.confidence_score < threshold => intent_name.clone(),
+        _ => return,
+    };
+    trace.push(PolicyEvent::IntentDemoted {
+        rule_index,
+        intent_name,
+        confidence_score: result.intent.confidence_score,
+    });
+    let promoted_index = result
+        .alternatives
+        .iter()
+        .position(|alternative| match alternative.intent.intent_name {
+            Some(ref intent_name) => promotable_intents.contains(intent_name),
+            None => false,
+        })
+        .or_else(|| {
+            result
+                .alternatives
+                .iter()
+                .position(|alternative| alternative.intent.intent_name.is_none())
+        });
+    let promoted = match promoted_index {
+        Some(index) => result.alternatives.remove(index),
+        None => IntentParserAlternative {
+            intent: IntentClassifierResult {
+                intent_name: None,
+                confidence_score: 1.0 - result.intent.confidence_score,
+            },
+            slots: vec![],
+        },
+    };
+    trace.push(PolicyEvent::AlternativePromoted {
+        rule_index,
+        intent_name: promoted.intent.intent_name.clone(),
+        confidence_score: promoted.intent.confidence_score,
+    });
+    let demoted = IntentParserAlternative {
+        intent: mem::replace(&mut result.intent, promoted.intent),
+        slots: mem::replace(&mut result.slots, promoted.slots),
+    };
+    // Alternatives are sorted by decreasing confidence, the demoted intent is inserted at its rank
+    let demoted_index = result.alternatives.partition_point(|alternative| {
+        alternative.intent.confidence_score >= demoted.intent.confidence_score
+    });
+    result.alternatives.insert(demoted_index, demoted);
+}
+
+fn apply_min_slot_confidence(
+    rule_index: usize,
+    threshold: f32,
+    result: &mut IntentParserResult,
+    trace: &mut Vec<PolicyEvent>,
+) {
+    let mut drop_slots = |path: &str, slots: &mut Vec<Slot>| {
+        let mut index = 0;
+        slots.retain(|slot| {
+            let slot_path = format!("{}slots[{}]", path, index);
+            index += 1;
+            match slot.confidence_score {
+                Some(confidence_score) if confidence_score < threshold => {
+                    trace.push(PolicyEvent::SlotDropped {
+                        rule_index,
+                        path: slot_path,
+                        slot_name: slot.slot_name.clone(),
+                        range: slot.range.clone(),
+                        confidence_score,
+                    });
+                    false
+                }
+                _ => true,
+            }
+        })
+    };
+    drop_slots("", &mut result.slots);
+    for (index, alternative) in result.alternatives.iter_mut().enumerate() {
+        drop_slots(&format!("alternatives[{}].", index), &mut alternative.slots);
+    }
+}
+
+impl IntentParserResult {
+    /// Confidence of the whole result, defined as the product of the confidence score of the
+    /// intent and of the confidence scores of the slots, slots without confidence score being
+    /// considered certain
+    pub fn confidence_score(&self) -> f32 {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.confidence_score)
+            .product::<f32>()
+            * self.intent.confidence_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{intent, slot};
+
+    fn result(
+        intent: IntentClassifierResult,
+        slots: Vec<Slot>,
+        alternatives: Vec<IntentParserAlternative>,
+    ) -> IntentParserResult {
+        IntentParserResult {
+            input: "value".to_string(),
+            intent,
+            slots,
+            alternatives,
+        }
+    }
+
+    fn policy() -> ConfidencePolicy {
+        ConfidencePolicy {
+            rules: vec![
+                PolicyRule::MinIntentConfidence {
+                    threshold: 0.4,
+                    promotable_intents: vec!["makeTea".to_string()],
+                },
+                PolicyRule::MinSlotConfidence { threshold: 0.2 },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_confident_result_is_unchanged() {
+        // Given
+        let result = result(
+            intent(Some("makeCoffee"), 0.8),
+            vec![
+                Slot {
+                    confidence_score: Some(0.5),
+                    ..slot("beverage", "beverage", 0..5)
+                },
+                slot("size", "size", 0..5),
+            ],
+            vec![],
+        );
+
+        // When
+        let outcome = policy().apply(&result);
+
+        // Then
+        assert_eq!(result, outcome.result);
+        assert_eq!(Vec::<PolicyEvent>::new(), outcome.trace);
+    }
+
+    #[test]
+    fn test_alternative_is_promoted() {
+        // Given
+        let result = result(
+            intent(Some("makeCoffee"), 0.35),
+            vec![Slot {
+                confidence_score: Some(0.9),
+                ..slot("beverage", "beverage", 0..5)
+            }],
+            vec![
+                IntentParserAlternative {
+                    intent: intent(None, 0.33),
+                    slots: vec![],
+                },
+                IntentParserAlternative {
+                    intent: intent(Some("makeTea"), 0.3),
+                    slots: vec![
+                        Slot {
+                            confidence_score: Some(0.1),
+                            ..slot("beverage", "beverage", 0..5)
+                        },
+                        Slot {
+                            confidence_score: Some(0.6),
+                            ..slot("size", "size", 0..5)
+                        },
+                    ],
+                },
+            ],
+        );
+
+        // When
+        let outcome = policy().apply(&result);
+
+        // Then
+        let expected_result = IntentParserResult {
+            input: "value".to_string(),
+            intent: intent(Some("makeTea"), 0.3),
+            slots: vec![Slot {
+                confidence_score: Some(0.6),
+                ..slot("size", "size", 0..5)
+            }],
+            alternatives: vec![
+                IntentParserAlternative {
+                    intent: intent(Some("makeCoffee"), 0.35),
+                    slots: vec![Slot {
+                        confidence_score: Some(0.9),
+                        ..slot("beverage", "beverage", 0..5)
+                    }],
+                },
+                IntentParserAlternative {
+                    intent: intent(None, 0.33),
+                    slots: vec![],
+                },
+            ],
+        };
+        let expected_trace = vec![
+            PolicyEvent::IntentDemoted {
+                rule_index: 0,
+                intent_name: "makeCoffee".to_string(),
+                confidence_score: 0.35,
+            },
+            PolicyEvent::AlternativePromoted {
+                rule_index: 0,
+                intent_name: Some("makeTea".to_string()),
+                confidence_score: 0.3,
+            },
+            PolicyEvent::SlotDropped {
+                rule_index: 1,
+                path: "slots[0]".to_string(),
+                slot_name: "beverage".to_string(),
+                range: 0..5,
+                confidence_score: 0.1,
+            },
+        ];
+        assert_eq!(expected_result, outcome.result);
+        assert_eq!(expected_trace, outcome.trace);
+    }
+
+    #[test]
+    fn test_demoted_intent_is_inserted_at_its_rank() {
+        // Given
+        let result = result(
+            intent(Some("makeCoffee"), 0.38),
+            vec![],
+            vec![
+                IntentParserAlternative {
+                    intent: intent(Some("makeChocolate"), 0.3),
+                    slots: vec![],
+                },
+                IntentParserAlternative {
+                    intent: intent(None, 0.2),
+                    slots: vec![],
+                },
+                IntentParserAlternative {
+                    intent: intent(Some("makeTea"), 0.1),
+                    slots: vec![],
+                },
+            ],
+        );
+
+        // When
+        let outcome = policy().apply(&result);
+
+        // Then
+        let alternative_intents = outcome
+            .result
+            .alternatives
+            .iter()
+            .map(|alternative| alternative.intent.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(intent(Some("makeTea"), 0.1), outcome.result.intent);
+        assert_eq!(
+            vec![
+                intent(Some("makeCoffee"), 0.38),
+                intent(Some("makeChocolate"), 0.3),
+                intent(None, 0.2),
+            ],
+            alternative_intents
+        );
+        assert!(alternative_intents
+            .windows(2)
+            .all(|pair| pair[0].confidence_score >= pair[1].confidence_score));
+    }
+
+    #[test]
+    fn test_intent_is_replaced_by_null_intent() {
+        // Given
+        let result = result(
+            intent(Some("makeCoffee"), 0.25),
+            vec![],
+            vec![IntentParserAlternative {
+                intent: intent(Some("makeChocolate"), 0.2),
+                slots: vec![],
+            }],
+        );
+
+        // When
+        let outcome = policy().apply(&result);
+
+        // Then
+        assert_eq!(intent(None, 0.75), outcome.result.intent);
+        assert_eq!(
+            vec![
+                IntentParserAlternative {
+                    intent: intent(Some("makeCoffee"), 0.25),
+                    slots: vec![],
+                },
+                IntentParserAlternative {
+                    intent: intent(Some("makeChocolate"), 0.2),
+                    slots: vec![],
+                },
+            ],
+            outcome.result.alternatives
+        );
+    }
+
+    #[test]
+    fn test_policy_deserialization() {
+        // Given
+        let json = r#"{
+            "rules": [
+                {"kind": "MinIntentConfidence", "threshold": 0.4, "promotableIntents": ["makeTea"]},
+                {"kind": "MinSlotConfidence", "threshold": 0.2}
+            ]
+        }"#;
+
+        // When
+        let policy: ConfidencePolicy = serde_json::from_str(json).unwrap();
+
+        // Then
+        assert_eq!(self::policy(), policy);
+    }
+
+    #[test]
+    fn test_result_confidence_score() {
+        // Given
+        let result = result(
+            intent(Some("makeCoffee"), 0.8),
+            vec![
+                Slot {
+                    confidence_score: Some(0.5),
+                    ..slot("beverage", "beverage", 0..5)
+                },
+                slot("size", "size", 0..5),
+            ],
+            vec![],
+        );
+
+        // When
+        let confidence_score = result.confidence_score();
+
+        // Then
+        assert_eq!(0.4, confidence_score);
+    }
+}
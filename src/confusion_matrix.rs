@@ -0,0 +1,358 @@
+//! Confusion matrix of intents, for the analysis of classification errors
+//!
+//! Rows of the matrix correspond to gold intents and columns to predicted ones, `None` being the
+//! null intent. Each cell keeps the inputs of its results when they are misclassified. Besides its
+//! JSON serialization, the matrix renders as plain-text tables laid out like those of the
+//! documentation, in which the null intent is labelled `<null>`.
+
+use crate::ontology::IntentParserResult;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Label of the null intent in tables, the angle brackets keeping it apart from intent names such
+/// as "None"
+const NULL_INTENT_LABEL: &str = "<null>";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfusionMatrixCell {
+    pub gold_intent: Option<String>,
+    pub predicted_intent: Option<String>,
+    pub count: usize,
+    /// Inputs of the results of the cell, which are only kept when the intents differ
+    pub misclassified_inputs: Vec<String>,
+}
+
+/// Confusion matrix of intents, in which only non-empty cells are stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfusionMatrix {
+    /// Gold and predicted intents, sorted with the null intent first
+    pub intents: Vec<Option<String>>,
+    pub cells: Vec<ConfusionMatrixCell>,
+}
+
+impl ConfusionMatrix {
+    pub fn cell(
+        &self,
+        gold_intent: Option<&str>,
+        predicted_intent: Option<&str>,
+    ) -> Option<&ConfusionMatrixCell> {
+        self.cells.iter().find(|cell| {
+            cell.gold_intent.as_deref() == gold_intent
+                && cell.predicted_intent.as_deref() == predicted_intent
+        })
+    }
+
+    pub fn count(&self, gold_intent: Option<&str>, predicted_intent: Option<&str>) -> usize {
+        self.cell(gold_intent, predicted_intent)
+            .map_or(0, |cell| cell.count)
+    }
+
+    /// Cells of misclassified results
+    pub fn misclassifications(&self) -> impl Iterator<Item = &ConfusionMatrixCell> {
+        self.cells
+            .iter()
+            .filter(|cell| cell.gold_intent != cell.predicted_intent)
+    }
+
+    /// Renders the counts of the matrix as a plain-text table, with gold intents as rows
+    pub fn to_table(&self) -> String {
+        let mut titles = vec!["Gold \\ Predicted".to_string()];
+        titles.extend(self.intents.iter().map(intent_label));
+        let rows = self
+            .intents
+            .iter()
+            .map(|gold_intent| {
+                let mut row = vec![intent_label(gold_intent)];
+                row.extend(self.intents.iter().map(|predicted_intent| {
+                    self.count(gold_intent.as_deref(), predicted_intent.as_deref())
+                        .to_string()
+                }));
+                row
+            })
+            .collect::<Vec<_>>();
+        render_table(&titles, &rows)
+    }
+
+    /// Renders the misclassified inputs as a plain-text table, one input per row
+    pub fn misclassifications_table(&self) -> String {
+        let titles = ["Gold", "Predicted", "Input"]
+            .iter()
+            .map(|title| title.to_string())
+            .collect::<Vec<_>>();
+        let rows = self
+            .misclassifications()
+            .flat_map(|cell| {
+                cell.misclassified_inputs.iter().map(move |input| {
+                    vec![
+                        intent_label(&cell.gold_intent),
+                        intent_label(&cell.predicted_intent),
+                        input.clone(),
+                    ]
+                })
+            })
+            .collect::<Vec<_>>();
+        render_table(&titles, &rows)
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_table())
+    }
+}
+
+fn intent_label(intent: &Option<String>) -> String {
+    intent
+        .clone()
+        .unwrap_or_else(|| NULL_INTENT_LABEL.to_string())
+}
+
+/// Renders a table with left-aligned cells, separating the titles with `=` and the rows with `-`
+fn render_table(titles: &[String], rows: &[Vec<String>]) -> String {
+    let widths = titles
+        .iter()
+        .enumerate()
+        .map(|(index, title)| {
+            rows.iter()
+                .map(|row| row[index].chars().count())
+                .chain(Some(title.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let separator = |fill: &str| {
+        let columns = widths
+            .iter()
+            .map(|width| fill.repeat(width + 2))
+            .collect::<Vec<_>>();
+        format!("+{}+\n", columns.join("+"))
+    };
+    let line = |cells: &[String]| {
+        let columns = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| {
+                let padding = " ".repeat(width - cell.chars().count());
+                format!(" {}{} ", cell, padding)
+            })
+            .collect::<Vec<_>>();
+        format!("|{}|\n", columns.join("|"))
+    };
+    let mut table = separator("-");
+    table.push_str(&line(titles));
+    table.push_str(&separator("="));
+    for row in rows {
+        table.push_str(&line(row));
+        table.push_str(&separator("-"));
+    }
+    table
+}
+
+/// Accumulates gold and predicted results into a confusion matrix
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfusionMatrixBuilder {
+    cells: BTreeMap<(Option<String>, Option<String>), ConfusionMatrixCell>,
+}
+
+impl ConfusionMatrixBuilder {
+    pub fn add(&mut self, gold: &IntentParserResult, predicted: &IntentParserResult) {
+        let gold_intent = gold.intent.intent_name.clone();
+        let predicted_intent = predicted.intent.intent_name.clone();
+        let misclassified = gold_intent != predicted_intent;
+        let cell = self
+            .cells
+            .entry((gold_intent.clone(), predicted_intent.clone()))
+            .or_insert_with(|| ConfusionMatrixCell {
+                gold_intent,
+                predicted_intent,
+                count: 0,
+                misclassified_inputs: vec![],
+            });
+        cell.count += 1;
+        if misclassified {
+            cell.misclassified_inputs.push(gold.input.clone());
+        }
+    }
+
+    pub fn build(&self) -> ConfusionMatrix {
+        let intents = self
+            .cells
+            .keys()
+            .flat_map(|(gold_intent, predicted_intent)| vec![gold_intent, predicted_intent])
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        ConfusionMatrix {
+            intents: intents.into_iter().collect(),
+            cells: self.cells.values().cloned().collect(),
+        }
+    }
+}
+
+/// Builds the confusion matrix of pairs of gold and predicted results
+pub fn confusion_matrix<'a, I>(results: I) -> ConfusionMatrix
+where
+    I: IntoIterator<Item = (&'a IntentParserResult, &'a IntentParserResult)>,
+{
+    let mut builder = ConfusionMatrixBuilder::default();
+    for (gold, predicted) in results {
+        builder.add(gold, predicted);
+    }
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::IntentClassifierResult;
+    use serde_json::json;
+
+    fn result(input: &str, intent_name: Option<&str>) -> IntentParserResult {
+        IntentParserResult {
+            input: input.to_string(),
+            intent: IntentClassifierResult {
+                intent_name: intent_name.map(|name| name.to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![],
+            alternatives: vec![],
+        }
+    }
+
+    fn results() -> Vec<(IntentParserResult, IntentParserResult)> {
+        vec![
+            (
+                result("make me a coffee", Some("makeCoffee")),
+                result("make me a coffee", Some("makeCoffee")),
+            ),
+            (
+                result("a cup of tea please", Some("makeTea")),
+                result("a cup of tea please", Some("makeCoffee")),
+            ),
+            (result("hello", None), result("hello", Some("makeTea"))),
+            (
+                result("tea time", Some("makeTea")),
+                result("tea time", Some("makeCoffee")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_confusion_matrix() {
+        // Given
+        let results = results();
+
+        // When
+        let matrix = confusion_matrix(results.iter().map(|(gold, predicted)| (gold, predicted)));
+
+        // Then
+        let expected_intents = vec![
+            None,
+            Some("makeCoffee".to_string()),
+            Some("makeTea".to_string()),
+        ];
+        assert_eq!(expected_intents, matrix.intents);
+        assert_eq!(1, matrix.count(Some("makeCoffee"), Some("makeCoffee")));
+        assert_eq!(2, matrix.count(Some("makeTea"), Some("makeCoffee")));
+        assert_eq!(0, matrix.count(Some("makeCoffee"), Some("makeTea")));
+        assert_eq!(
+            vec!["hello".to_string()],
+            matrix
+                .cell(None, Some("makeTea"))
+                .unwrap()
+                .misclassified_inputs
+        );
+        assert_eq!(
+            Vec::<String>::new(),
+            matrix
+                .cell(Some("makeCoffee"), Some("makeCoffee"))
+                .unwrap()
+                .misclassified_inputs
+        );
+        assert_eq!(2, matrix.misclassifications().count());
+    }
+
+    #[test]
+    fn test_confusion_matrix_serialization() {
+        // Given
+        let gold = result("hello", None);
+        let predicted = result("hello", Some("makeTea"));
+
+        // When
+        let matrix = confusion_matrix([(&gold, &predicted)]);
+
+        // Then
+        let expected_json = json!({
+            "intents": [null, "makeTea"],
+            "cells": [{
+                "goldIntent": null,
+                "predictedIntent": "makeTea",
+                "count": 1,
+                "misclassifiedInputs": ["hello"]
+            }]
+        });
+        assert_eq!(expected_json, serde_json::to_value(&matrix).unwrap());
+        assert_eq!(matrix, serde_json::from_value(expected_json).unwrap());
+    }
+
+    #[test]
+    fn test_tables() {
+        // Given
+        let results = results();
+        let matrix = confusion_matrix(results.iter().map(|(gold, predicted)| (gold, predicted)));
+
+        // When
+        let table = matrix.to_table();
+        let misclassifications_table = matrix.misclassifications_table();
+
+        // Then
+        let expected_table = "\
++------------------+--------+------------+---------+
+| Gold \\ Predicted | <null> | makeCoffee | makeTea |
++==================+========+============+=========+
+| <null>           | 0      | 0          | 1       |
++------------------+--------+------------+---------+
+| makeCoffee       | 0      | 1          | 0       |
++------------------+--------+------------+---------+
+| makeTea          | 0      | 2          | 0       |
++------------------+--------+------------+---------+
+";
+        let expected_misclassifications_table = "\
++---------+------------+---------------------+
+| Gold    | Predicted  | Input               |
++=========+============+=====================+
+| <null>  | makeTea    | hello               |
++---------+------------+---------------------+
+| makeTea | makeCoffee | a cup of tea please |
++---------+------------+---------------------+
+| makeTea | makeCoffee | tea time            |
++---------+------------+---------------------+
+";
+        assert_eq!(expected_table, table);
+        assert_eq!(expected_table, matrix.to_string());
+        assert_eq!(expected_misclassifications_table, misclassifications_table);
+    }
+
+    #[test]
+    fn test_null_intent_is_not_mixed_up_with_an_intent_named_none() {
+        // Given
+        let results = [(result("hello", None), result("hello", Some("None")))];
+        let matrix = confusion_matrix(results.iter().map(|(gold, predicted)| (gold, predicted)));
+
+        // When
+        let table = matrix.to_table();
+
+        // Then
+        let expected_table = "\
++------------------+--------+------+
+| Gold \\ Predicted | <null> | None |
++==================+========+======+
+| <null>           | 0      | 1    |
++------------------+--------+------+
+| None             | 0      | 0    |
++------------------+--------+------+
+";
+        assert_eq!(vec![None, Some("None".to_string())], matrix.intents);
+        assert_eq!(expected_table, table);
+    }
+}
@@ -0,0 +1,338 @@
+use crate::errors::*;
+use crate::ontology::AmountOfMoneyValue;
+
+/// ISO 4217 currencies which can be resolved from the unit of an `AmountOfMoneyValue`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum Currency {
+    AUD,
+    BRL,
+    CAD,
+    CHF,
+    CNY,
+    DKK,
+    EUR,
+    GBP,
+    HKD,
+    INR,
+    JPY,
+    KRW,
+    MXN,
+    NOK,
+    NZD,
+    PLN,
+    RUB,
+    SEK,
+    SGD,
+    USD,
+}
+
+/// Currency resolved from a free-form unit
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResolvedCurrency {
+    pub currency: Currency,
+    /// Whether the unit is shared by several currencies, e.g. "$", in which case `currency` is
+    /// only the most likely one
+    pub ambiguous: bool,
+}
+
+/// Symbols and names used to express amounts of money, along with the currency they most likely
+/// refer to and whether they are ambiguous. Names are matched case-insensitively.
+const CURRENCY_ALIASES: &[(&str, Currency, bool)] = &[
+    ("$", Currency::USD, true),
+    ("us$", Currency::USD, false),
+    ("dollar", Currency::USD, true),
+    ("dollars", Currency::USD, true),
+    ("a$", Currency::AUD, false),
+    ("au$", Currency::AUD, false),
+    ("c$", Currency::CAD, false),
+    ("ca$", Currency::CAD, false),
+    ("hk$", Currency::HKD, false),
+    ("nz$", Currency::NZD, false),
+    ("s$", Currency::SGD, false),
+    ("mx$", Currency::MXN, false),
+    ("r$", Currency::BRL, false),
+    ("real", Currency::BRL, false),
+    ("reais", Currency::BRL, false),
+    ("€", Currency::EUR, false),
+    ("euro", Currency::EUR, false),
+    ("euros", Currency::EUR, false),
+    ("£", Currency::GBP, true),
+    ("pound", Currency::GBP, true),
+    ("pounds", Currency::GBP, true),
+    ("¥", Currency::JPY, true),
+    ("yen", Currency::JPY, false),
+    ("円", Currency::JPY, false),
+    ("yuan", Currency::CNY, false),
+    ("元", Currency::CNY, false),
+    ("₩", Currency::KRW, false),
+    ("won", Currency::KRW, false),
+    ("원", Currency::KRW, false),
+    ("₹", Currency::INR, false),
+    ("rupee", Currency::INR, true),
+    ("rupees", Currency::INR, true),
+    ("₽", Currency::RUB, false),
+    ("rouble", Currency::RUB, false),
+    ("roubles", Currency::RUB, false),
+    ("ruble", Currency::RUB, false),
+    ("rubles", Currency::RUB, false),
+    ("fr", Currency::CHF, true),
+    ("franc", Currency::CHF, true),
+    ("francs", Currency::CHF, true),
+    ("kr", Currency::SEK, true),
+    ("krona", Currency::SEK, false),
+    ("kronor", Currency::SEK, false),
+    ("krone", Currency::NOK, true),
+    ("kroner", Currency::NOK, true),
+    ("zł", Currency::PLN, false),
+    ("zloty", Currency::PLN, false),
+];
+
+impl Currency {
+    pub fn all() -> &'static [Currency] {
+        static ALL: &[Currency] = &[
+            Currency::AUD,
+            Currency::BRL,
+            Currency::CAD,
+            Currency::CHF,
+            Currency::CNY,
+            Currency::DKK,
+            Currency::EUR,
+            Currency::GBP,
+            Currency::HKD,
+            Currency::INR,
+            Currency::JPY,
+            Currency::KRW,
+            Currency::MXN,
+            Currency::NOK,
+            Currency::NZD,
+            Currency::PLN,
+            Currency::RUB,
+            Currency::SEK,
+            Currency::SGD,
+            Currency::USD,
+        ];
+        ALL
+    }
+
+    /// Alphabetic ISO 4217 code of the currency
+    pub fn iso_code(&self) -> &'static str {
+        match *self {
+            Currency::AUD => "AUD",
+            Currency::BRL => "BRL",
+            Currency::CAD => "CAD",
+            Currency::CHF => "CHF",
+            Currency::CNY => "CNY",
+            Currency::DKK => "DKK",
+            Currency::EUR => "EUR",
+            Currency::GBP => "GBP",
+            Currency::HKD => "HKD",
+            Currency::INR => "INR",
+            Currency::JPY => "JPY",
+            Currency::KRW => "KRW",
+            Currency::MXN => "MXN",
+            Currency::NOK => "NOK",
+            Currency::NZD => "NZD",
+            Currency::PLN => "PLN",
+            Currency::RUB => "RUB",
+            Currency::SEK => "SEK",
+            Currency::SGD => "SGD",
+            Currency::USD => "USD",
+        }
+    }
+
+    pub fn full_name(&self) -> &'static str {
+        match *self {
+            Currency::AUD => "Australian dollar",
+            Currency::BRL => "Brazilian real",
+            Currency::CAD => "Canadian dollar",
+            Currency::CHF => "Swiss franc",
+            Currency::CNY => "Chinese yuan",
+            Currency::DKK => "Danish krone",
+            Currency::EUR => "Euro",
+            Currency::GBP => "Pound sterling",
+            Currency::HKD => "Hong Kong dollar",
+            Currency::INR => "Indian rupee",
+            Currency::JPY => "Japanese yen",
+            Currency::KRW => "South Korean won",
+            Currency::MXN => "Mexican peso",
+            Currency::NOK => "Norwegian krone",
+            Currency::NZD => "New Zealand dollar",
+            Currency::PLN => "Polish złoty",
+            Currency::RUB => "Russian ruble",
+            Currency::SEK => "Swedish krona",
+            Currency::SGD => "Singapore dollar",
+            Currency::USD => "United States dollar",
+        }
+    }
+
+    /// Number of digits after the decimal separator used by the minor unit of the currency, as
+    /// defined by ISO 4217
+    pub fn minor_unit_exponent(&self) -> u8 {
+        match *self {
+            Currency::JPY | Currency::KRW => 0,
+            _ => 2,
+        }
+    }
+
+    pub fn from_iso_code(code: &str) -> Result<Self> {
+        Currency::all()
+            .iter()
+            .find(|currency| currency.iso_code().eq_ignore_ascii_case(code))
+            .cloned()
+            .ok_or_else(|| {
+                OntologyError::conversion_failure(format!(
+                    "Unknown ISO 4217 currency code: {}",
+                    code
+                ))
+            })
+    }
+
+    /// Resolves a free-form unit, which can be an ISO 4217 code, a symbol or a currency name
+    pub fn from_unit(unit: &str) -> Option<ResolvedCurrency> {
+        let unit = unit.trim();
+        if let Ok(currency) = Currency::from_iso_code(unit) {
+            return Some(ResolvedCurrency {
+                currency,
+                ambiguous: false,
+            });
+        }
+        let lowercased_unit = unit.to_lowercase();
+        CURRENCY_ALIASES
+            .iter()
+            .find(|(alias, _, _)| *alias == lowercased_unit)
+            .map(|(_, currency, ambiguous)| ResolvedCurrency {
+                currency: *currency,
+                ambiguous: *ambiguous,
+            })
+    }
+}
+
+impl AmountOfMoneyValue {
+    /// Resolves the currency of the amount from its unit
+    pub fn currency(&self) -> Option<ResolvedCurrency> {
+        self.unit
+            .as_ref()
+            .and_then(|unit| Currency::from_unit(unit))
+    }
+
+    /// Returns a copy of the amount whose unit is the ISO 4217 code of `currency`
+    pub fn with_currency(self, currency: Currency) -> AmountOfMoneyValue {
+        AmountOfMoneyValue {
+            unit: Some(currency.iso_code().to_string()),
+            ..self
+        }
+    }
+
+    /// Returns the amount expressed in the minor unit of its currency, e.g. cents for euros
+    pub fn minor_units(&self) -> Option<i64> {
+        self.currency().map(|resolved| {
+            let factor = 10_f64.powi(resolved.currency.minor_unit_exponent() as i32);
+            (f64::from(self.value) * factor).round() as i64
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::{Precision, SlotValue};
+    use crate::BuiltinEntityKind;
+
+    #[test]
+    fn test_from_unit() {
+        assert_eq!(
+            Some(ResolvedCurrency {
+                currency: Currency::EUR,
+                ambiguous: false
+            }),
+            Currency::from_unit("€")
+        );
+        assert_eq!(
+            Some(ResolvedCurrency {
+                currency: Currency::USD,
+                ambiguous: true
+            }),
+            Currency::from_unit("$")
+        );
+        assert_eq!(
+            Some(ResolvedCurrency {
+                currency: Currency::JPY,
+                ambiguous: false
+            }),
+            Currency::from_unit("jpy")
+        );
+        assert_eq!(
+            Some(ResolvedCurrency {
+                currency: Currency::EUR,
+                ambiguous: false
+            }),
+            Currency::from_unit(" Euros")
+        );
+        assert_eq!(None, Currency::from_unit("cent"));
+    }
+
+    #[test]
+    fn test_currency_of_result_description() {
+        // Given
+        let values: Vec<SlotValue> =
+            serde_json::from_str(&BuiltinEntityKind::AmountOfMoney.result_description()).unwrap();
+
+        // When
+        let currencies = values
+            .into_iter()
+            .map(|value| match value {
+                SlotValue::AmountOfMoney(amount) => amount.currency().map(|c| c.currency),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Then
+        assert_eq!(vec![Some(Currency::EUR)], currencies);
+    }
+
+    #[test]
+    fn test_minor_units() {
+        // Given
+        let euros = AmountOfMoneyValue {
+            value: 10.05,
+            precision: Precision::Approximate,
+            unit: Some("€".to_string()),
+        };
+        let yens = AmountOfMoneyValue {
+            value: 1234.,
+            precision: Precision::Exact,
+            unit: Some("¥".to_string()),
+        };
+        let unknown = AmountOfMoneyValue {
+            value: 3.,
+            precision: Precision::Exact,
+            unit: None,
+        };
+
+        // When/Then
+        assert_eq!(Some(1005), euros.minor_units());
+        assert_eq!(Some(1234), yens.minor_units());
+        assert_eq!(None, unknown.minor_units());
+    }
+
+    #[test]
+    fn test_with_currency_keeps_unit_string() {
+        // Given
+        let amount = AmountOfMoneyValue {
+            value: 10.,
+            precision: Precision::Exact,
+            unit: Some("$".to_string()),
+        };
+
+        // When
+        let amount = amount.with_currency(Currency::CAD);
+
+        // Then
+        assert_eq!(
+            r#"{"value":10.0,"precision":"Exact","unit":"CAD"}"#,
+            serde_json::to_string(&amount).unwrap()
+        );
+        assert_eq!(Currency::CAD, amount.currency().unwrap().currency);
+    }
+}
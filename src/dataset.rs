@@ -0,0 +1,296 @@
+//! Training dataset of the Snips NLU engine
+//!
+//! These types map the JSON dataset format consumed by the engine:
+//!
+//! ```json
+//! {
+//!   "language": "en",
+//!   "intents": {
+//!     "turnLightOn": {
+//!       "utterances": [
+//!         {
+//!           "data": [
+//!             {"text": "turn on the lights in the "},
+//!             {"text": "kitchen", "entity": "room", "slot_name": "room"}
+//!           ]
+//!         }
+//!       ]
+//!     }
+//!   },
+//!   "entities": {
+//!     "room": {
+//!       "data": [{"value": "kitchen", "synonyms": ["cooking room"]}],
+//!       "use_synonyms": true,
+//!       "automatically_extensible": false,
+//!       "matching_strictness": 1.0
+//!     },
+//!     "snips/number": {}
+//!   }
+//! }
+//! ```
+//!
+//! Builtin entities are declared with an empty object under their identifier, they are exposed
+//! separately from custom entities in `Dataset`. Whether an entity is builtin or custom is decided
+//! from its identifier.
+
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use crate::entity::custom_entity::CustomEntity;
+use crate::errors::*;
+use crate::language::Language;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Training dataset of an NLU engine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawDataset", into = "RawDataset")]
+pub struct Dataset {
+    pub language: Language,
+    pub intents: BTreeMap<String, Intent>,
+    pub custom_entities: BTreeMap<String, CustomEntity>,
+    pub builtin_entities: Vec<BuiltinEntityKind>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Intent {
+    pub utterances: Vec<Utterance>,
+}
+
+/// Training utterance, made of chunks of text some of which are annotated with a slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Utterance {
+    pub data: Vec<UtteranceChunk>,
+}
+
+impl Utterance {
+    /// Text of the utterance
+    pub fn text(&self) -> String {
+        self.data.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtteranceChunk {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<EntityReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot_name: Option<String>,
+}
+
+/// Reference to an entity, serialized as the builtin entity identifier or the custom entity name
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EntityReference {
+    Builtin(BuiltinEntityKind),
+    Custom(String),
+}
+
+impl EntityReference {
+    pub fn identifier(&self) -> &str {
+        match self {
+            EntityReference::Builtin(kind) => kind.identifier(),
+            EntityReference::Custom(name) => name,
+        }
+    }
+
+    /// Parses an identifier, names which are not builtin entity identifiers referring to custom
+    /// entities
+    pub fn from_identifier(identifier: &str) -> EntityReference {
+        BuiltinEntityKind::from_identifier(identifier)
+            .map(EntityReference::Builtin)
+            .unwrap_or_else(|_| EntityReference::Custom(identifier.to_string()))
+    }
+}
+
+impl fmt::Display for EntityReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.identifier())
+    }
+}
+
+impl Serialize for EntityReference {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.identifier())
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityReference {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(|identifier| Self::from_identifier(&identifier))
+    }
+}
+
+/// JSON layout of the dataset, in which the language is a lowercase identifier and builtin
+/// entities are mixed with custom entities
+#[derive(Serialize, Deserialize)]
+struct RawDataset {
+    language: String,
+    intents: BTreeMap<String, Intent>,
+    entities: BTreeMap<String, Value>,
+}
+
+impl TryFrom<RawDataset> for Dataset {
+    type Error = OntologyError;
+
+    fn try_from(dataset: RawDataset) -> Result<Self> {
+        let mut custom_entities = BTreeMap::new();
+        let mut builtin_entities = vec![];
+        for (identifier, entity) in dataset.entities {
+            match EntityReference::from_identifier(&identifier) {
+                EntityReference::Builtin(kind) => {
+                    if entity != json!({}) {
+                        return Err(OntologyError::conversion_failure(format!(
+                            "Builtin entity {} cannot be defined in the dataset",
+                            identifier
+                        )));
+                    }
+                    builtin_entities.push(kind)
+                }
+                EntityReference::Custom(name) => {
+                    let entity = serde_json::from_value(entity).map_err(|error| {
+                        OntologyError::conversion_failure(format!(
+                            "Invalid definition of custom entity {}: {}",
+                            identifier, error
+                        ))
+                    })?;
+                    custom_entities.insert(name, entity);
+                }
+            }
+        }
+        Ok(Dataset {
+            language: Language::from_str(&dataset.language)?,
+            intents: dataset.intents,
+            custom_entities,
+            builtin_entities,
+        })
+    }
+}
+
+impl From<Dataset> for RawDataset {
+    fn from(dataset: Dataset) -> Self {
+        let builtin_entities = dataset
+            .builtin_entities
+            .into_iter()
+            .map(|kind| (kind.identifier().to_string(), json!({})));
+        let custom_entities = dataset
+            .custom_entities
+            .into_iter()
+            .map(|(name, entity)| (name, json!(entity)));
+        RawDataset {
+            language: dataset.language.to_string(),
+            intents: dataset.intents,
+            entities: builtin_entities.chain(custom_entities).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_deserialization() {
+        // Given
+        let json = include_str!("../fixtures/dataset/dataset.json");
+
+        // When
+        let dataset: Dataset = serde_json::from_str(json).unwrap();
+
+        // Then
+        assert_eq!(Language::PT_BR, dataset.language);
+        assert_eq!(vec![BuiltinEntityKind::Number], dataset.builtin_entities);
+        let beverage = &dataset.custom_entities["beverage"];
+        assert_eq!(0.5, beverage.matching_strictness);
+        assert!(beverage.use_synonyms);
+        assert!(!beverage.automatically_extensible);
+        assert_eq!(
+            vec!["cafezinho".to_string()],
+            beverage.data[0].synonyms.clone()
+        );
+        let utterance = &dataset.intents["fazerCafe"].utterances[0];
+        assert_eq!("faça 2 cafés por favor", utterance.text());
+        assert_eq!(
+            Some(EntityReference::Builtin(BuiltinEntityKind::Number)),
+            utterance.data[1].entity
+        );
+        assert_eq!(
+            Some(EntityReference::Custom("beverage".to_string())),
+            utterance.data[3].entity
+        );
+        assert_eq!(None, utterance.data[4].slot_name);
+    }
+
+    #[test]
+    fn test_dataset_round_trip() {
+        // Given
+        let json = include_str!("../fixtures/dataset/dataset.json");
+        let dataset: Dataset = serde_json::from_str(json).unwrap();
+
+        // When
+        let serialized = serde_json::to_value(&dataset).unwrap();
+
+        // Then
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(json).unwrap(),
+            serialized
+        );
+        assert_eq!(dataset, serde_json::from_value(serialized).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_entities_are_rejected() {
+        // Given
+        let customized_builtin = r#"{
+            "language": "en",
+            "intents": {},
+            "entities": {
+                "snips/number": {"data": [], "use_synonyms": true, "automatically_extensible": true}
+            }
+        }"#;
+        let undefined_custom = r#"{
+            "language": "en",
+            "intents": {},
+            "entities": {"beverage": {}}
+        }"#;
+
+        // When/Then
+        assert!(serde_json::from_str::<Dataset>(customized_builtin).is_err());
+        assert!(serde_json::from_str::<Dataset>(undefined_custom).is_err());
+    }
+
+    #[test]
+    fn test_invalid_custom_entity_error_is_reported() {
+        // Given
+        let misspelled_custom = r#"{
+            "language": "en",
+            "intents": {},
+            "entities": {
+                "beverage": {"data": [], "use_synonym": true, "automatically_extensible": true}
+            }
+        }"#;
+
+        // When
+        let error = serde_json::from_str::<Dataset>(misspelled_custom)
+            .unwrap_err()
+            .to_string();
+
+        // Then
+        assert!(
+            error.starts_with(
+                "Invalid definition of custom entity beverage: missing field `use_synonyms`"
+            ),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! Structured differences between two intent parsing results
+//!
+//! This is meant for regression reports, e.g. when replaying utterances against two versions of
+//! an NLU engine. Slots of both results are matched by range and slot name, each slot being
+//! matched at most once in the order of the slots, and the changes of matched slots are reported
+//! per field, using the paths of the JSON representation such as
+//! `value.grain`. Alternatives are not compared.
+
+use crate::ontology::{IntentParserResult, Slot};
+use serde::Serialize;
+use serde_json::Value;
+use std::ops::Range;
+
+/// Difference between two intent parsing results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Change {
+    /// The inputs of both results differ
+    InputChanged { before: String, after: String },
+    /// The intent has changed, `None` being the null intent
+    IntentChanged {
+        before: Option<String>,
+        after: Option<String>,
+    },
+    /// The confidence score of the intent has changed by more than the tolerance
+    IntentConfidenceScoreChanged { before: f32, after: f32, delta: f32 },
+    /// The slot is only found in the second result
+    SlotAdded { slot: Slot },
+    /// The slot is only found in the first result
+    SlotRemoved { slot: Slot },
+    /// A field of a slot found in both results has changed
+    #[serde(rename_all = "camelCase")]
+    SlotChanged {
+        slot_name: String,
+        range: Range<usize>,
+        field: String,
+        before: Value,
+        after: Value,
+    },
+}
+
+/// Differences between two intent parsing results, in the order of the fields of the results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultDiff {
+    pub changes: Vec<Change>,
+}
+
+impl ResultDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+fn is_same_slot(slot: &Slot, other: &Slot) -> bool {
+    slot.range == other.range && slot.slot_name == other.slot_name
+}
+
+fn scores_differ(before: f32, after: f32, tolerance: f32) -> bool {
+    (after - before).abs() > tolerance
+}
+
+/// Collects the fields which differ between two JSON values
+///
+/// Objects are compared field by field, unless they are tagged with different kinds, e.g. a
+/// `Number` slot value and a `Custom` one, in which case they are reported as a whole.
+fn diff_json_values(
+    field: String,
+    before: &Value,
+    after: &Value,
+    fields: &mut Vec<(String, Value, Value)>,
+) {
+    match (before, after) {
+        (Value::Object(before_fields), Value::Object(after_fields))
+            if before_fields.get("kind") == after_fields.get("kind") =>
+        {
+            let mut names = before_fields
+                .keys()
+                .chain(after_fields.keys())
+                .collect::<Vec<_>>();
+            names.sort();
+            names.dedup();
+            for name in names {
+                diff_json_values(
+                    format!("{}.{}", field, name),
+                    before_fields.get(name).unwrap_or(&Value::Null),
+                    after_fields.get(name).unwrap_or(&Value::Null),
+                    fields,
+                )
+            }
+        }
+        _ if before != after => fields.push((field, before.clone(), after.clone())),
+        _ => (),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn diff_slots(before: &Slot, after: &Slot, confidence_tolerance: f32, changes: &mut Vec<Change>) {
+    let mut fields = vec![];
+    if before.raw_value != after.raw_value {
+        fields.push((
+            "rawValue".to_string(),
+            Value::from(before.raw_value.clone()),
+            Value::from(after.raw_value.clone()),
+        ))
+    }
+    if before.entity != after.entity {
+        fields.push((
+            "entity".to_string(),
+            Value::from(before.entity.clone()),
+            Value::from(after.entity.clone()),
+        ))
+    }
+    diff_json_values(
+        "value".to_string(),
+        &to_json(&before.value),
+        &to_json(&after.value),
+        &mut fields,
+    );
+    if before.alternatives != after.alternatives {
+        fields.push((
+            "alternatives".to_string(),
+            to_json(&before.alternatives),
+            to_json(&after.alternatives),
+        ))
+    }
+    let confidence_scores_differ = match (before.confidence_score, after.confidence_score) {
+        (Some(before), Some(after)) => scores_differ(before, after, confidence_tolerance),
+        (before, after) => before.is_some() != after.is_some(),
+    };
+    if confidence_scores_differ {
+        fields.push((
+            "confidenceScore".to_string(),
+            to_json(&before.confidence_score),
+            to_json(&after.confidence_score),
+        ))
+    }
+    changes.extend(
+        fields
+            .into_iter()
+            .map(|(field, before_value, after_value)| Change::SlotChanged {
+                slot_name: after.slot_name.clone(),
+                range: after.range.clone(),
+                field,
+                before: before_value,
+                after: after_value,
+            }),
+    );
+}
+
+impl IntentParserResult {
+    /// Returns the differences between this result and `other`
+    ///
+    /// Changes of confidence scores, of the intent as well as of the slots, are only reported when
+    /// they exceed `confidence_tolerance`.
+    pub fn diff(&self, other: &IntentParserResult, confidence_tolerance: f32) -> ResultDiff {
+        let mut changes = vec![];
+        if self.input != other.input {
+            changes.push(Change::InputChanged {
+                before: self.input.clone(),
+                after: other.input.clone(),
+            })
+        }
+        if self.intent.intent_name != other.intent.intent_name {
+            changes.push(Change::IntentChanged {
+                before: self.intent.intent_name.clone(),
+                after: other.intent.intent_name.clone(),
+            })
+        }
+        let before_score = self.intent.confidence_score;
+        let after_score = other.intent.confidence_score;
+        if scores_differ(before_score, after_score, confidence_tolerance) {
+            changes.push(Change::IntentConfidenceScoreChanged {
+                before: before_score,
+                after: after_score,
+                delta: after_score - before_score,
+            })
+        }
+        let mut matched_other_slots = vec![false; other.slots.len()];
+        for slot in &self.slots {
+            let other_index = other
+                .slots
+                .iter()
+                .enumerate()
+                .position(|(index, other_slot)| {
+                    !matched_other_slots[index] && is_same_slot(slot, other_slot)
+                });
+            match other_index {
+                Some(other_index) => {
+                    matched_other_slots[other_index] = true;
+                    let other_slot = &other.slots[other_index];
+                    diff_slots(slot, other_slot, confidence_tolerance, &mut changes)
+                }
+                None => changes.push(Change::SlotRemoved { slot: slot.clone() }),
+            }
+        }
+        for (other_slot, is_matched) in other.slots.iter().zip(matched_other_slots) {
+            if !is_matched {
+                changes.push(Change::SlotAdded {
+                    slot: other_slot.clone(),
+                })
+            }
+        }
+        ResultDiff { changes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::*;
+    use serde_json::json;
+
+    fn date_slot(grain: Grain, confidence_score: Option<f32>) -> Slot {
+        Slot {
+            raw_value: "tomorrow".to_string(),
+            value: SlotValue::InstantTime(InstantTimeValue {
+                value: "2017-06-14 00:00:00 +02:00".to_string(),
+                grain,
+                precision: Precision::Exact,
+            }),
+            alternatives: vec![],
+            range: 15..23,
+            entity: "snips/datetime".to_string(),
+            slot_name: "date".to_string(),
+            confidence_score,
+        }
+    }
+
+    fn result(intent_name: &str, confidence_score: f32, slots: Vec<Slot>) -> IntentParserResult {
+        IntentParserResult {
+            input: "book a table 4 tomorrow".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some(intent_name.to_string()),
+                confidence_score,
+            },
+            slots,
+            alternatives: vec![],
+        }
+    }
+
+    fn number_slot(range: Range<usize>) -> Slot {
+        Slot {
+            raw_value: "4".to_string(),
+            value: SlotValue::Number(NumberValue { value: 4. }),
+            alternatives: vec![],
+            range,
+            entity: "snips/number".to_string(),
+            slot_name: "party_size".to_string(),
+            confidence_score: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_results_have_no_diff() {
+        // Given
+        let before = result(
+            "bookRestaurant",
+            0.8,
+            vec![date_slot(Grain::Day, Some(0.9))],
+        );
+        let after = result(
+            "bookRestaurant",
+            0.805,
+            vec![date_slot(Grain::Day, Some(0.895))],
+        );
+
+        // When
+        let diff = before.diff(&after, 0.01);
+
+        // Then
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        // Given
+        let before = result(
+            "bookRestaurant",
+            0.8,
+            vec![date_slot(Grain::Day, Some(0.9)), number_slot(13..14)],
+        );
+        let after = result(
+            "bookTable",
+            0.5,
+            vec![date_slot(Grain::Hour, None), number_slot(12..14)],
+        );
+
+        // When
+        let diff = before.diff(&after, 0.01);
+
+        // Then
+        let expected_changes = vec![
+            Change::IntentChanged {
+                before: Some("bookRestaurant".to_string()),
+                after: Some("bookTable".to_string()),
+            },
+            Change::IntentConfidenceScoreChanged {
+                before: 0.8,
+                after: 0.5,
+                delta: 0.5 - 0.8,
+            },
+            Change::SlotChanged {
+                slot_name: "date".to_string(),
+                range: 15..23,
+                field: "value.grain".to_string(),
+                before: json!("Day"),
+                after: json!("Hour"),
+            },
+            Change::SlotChanged {
+                slot_name: "date".to_string(),
+                range: 15..23,
+                field: "confidenceScore".to_string(),
+                before: serde_json::to_value(Some(0.9_f32)).unwrap(),
+                after: Value::Null,
+            },
+            Change::SlotRemoved {
+                slot: number_slot(13..14),
+            },
+            Change::SlotAdded {
+                slot: number_slot(12..14),
+            },
+        ];
+        assert_eq!(expected_changes, diff.changes);
+    }
+
+    #[test]
+    fn test_slots_with_the_same_range_and_name_are_matched_once() {
+        // Given
+        let mut other_number_slot = number_slot(13..14);
+        other_number_slot.value = SlotValue::Number(NumberValue { value: 5. });
+        let before = result(
+            "bookRestaurant",
+            0.8,
+            vec![number_slot(13..14), other_number_slot.clone()],
+        );
+        let after = result("bookRestaurant", 0.8, vec![number_slot(13..14)]);
+
+        // When
+        let removal_diff = before.diff(&after, 0.01);
+        let addition_diff = after.diff(&before, 0.01);
+
+        // Then
+        assert_eq!(
+            vec![Change::SlotRemoved {
+                slot: other_number_slot.clone()
+            }],
+            removal_diff.changes
+        );
+        assert_eq!(
+            vec![Change::SlotAdded {
+                slot: other_number_slot
+            }],
+            addition_diff.changes
+        );
+    }
+
+    #[test]
+    fn test_slot_value_kind_change_is_reported_as_a_whole() {
+        // Given
+        let mut custom_slot = number_slot(13..14);
+        custom_slot.value = SlotValue::Custom("4".into());
+        let before = result("bookRestaurant", 0.8, vec![number_slot(13..14)]);
+        let after = result("bookRestaurant", 0.8, vec![custom_slot]);
+
+        // When
+        let diff = before.diff(&after, 0.01);
+
+        // Then
+        let expected_json = json!({
+            "changes": [{
+                "kind": "SlotChanged",
+                "slotName": "party_size",
+                "range": {"start": 13, "end": 14},
+                "field": "value",
+                "before": {"kind": "Number", "value": 4.0},
+                "after": {"kind": "Custom", "value": "4"}
+            }]
+        });
+        assert_eq!(expected_json, serde_json::to_value(&diff).unwrap());
+    }
+}
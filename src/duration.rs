@@ -0,0 +1,569 @@
+use crate::errors::*;
+use crate::ontology::{DurationValue, Precision};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Duration, FixedOffset, Months};
+#[cfg(feature = "chrono")]
+use std::cmp::Ordering;
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const MINUTES_PER_HOUR: i64 = 60;
+const HOURS_PER_DAY: i64 = 24;
+const DAYS_PER_WEEK: i64 = 7;
+const MONTHS_PER_QUARTER: i64 = 3;
+const QUARTERS_PER_YEAR: i64 = 4;
+
+impl Precision {
+    /// Combines the precisions of two values used together, the result being exact only when
+    /// both values are exact
+    pub fn combine(self, other: Precision) -> Precision {
+        match (self, other) {
+            (Precision::Exact, Precision::Exact) => Precision::Exact,
+            _ => Precision::Approximate,
+        }
+    }
+}
+
+/// Number of seconds of a duration, which is approximate when the duration is approximate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DurationSeconds {
+    pub seconds: i64,
+    pub precision: Precision,
+}
+
+impl DurationValue {
+    /// Returns an exact duration with all units set to zero
+    pub fn zero() -> DurationValue {
+        DurationValue {
+            years: 0,
+            quarters: 0,
+            months: 0,
+            weeks: 0,
+            days: 0,
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+            precision: Precision::Exact,
+        }
+    }
+
+    /// Returns whether the duration uses calendar units (years, quarters or months), whose length
+    /// in seconds depends on the date they are applied to
+    pub fn has_calendar_units(&self) -> bool {
+        self.years != 0 || self.quarters != 0 || self.months != 0
+    }
+
+    fn overflow(&self) -> OntologyError {
+        OntologyError::conversion_failure(format!("Duration overflow: {:?}", self))
+    }
+
+    /// Carries overflowing units over to the larger ones, e.g. 90 minutes becomes 1 hour and 30
+    /// minutes
+    ///
+    /// Days are never converted into months, as the number of days in a month is not fixed.
+    pub fn normalize(&self) -> Result<DurationValue> {
+        let carry = |value: i64, carried: i64, factor: i64| {
+            value
+                .checked_add(carried / factor)
+                .ok_or_else(|| self.overflow())
+        };
+        let minutes = carry(self.minutes, self.seconds, SECONDS_PER_MINUTE)?;
+        let hours = carry(self.hours, minutes, MINUTES_PER_HOUR)?;
+        let days = carry(self.days, hours, HOURS_PER_DAY)?;
+        let weeks = carry(self.weeks, days, DAYS_PER_WEEK)?;
+        let quarters = carry(self.quarters, self.months, MONTHS_PER_QUARTER)?;
+        let years = carry(self.years, quarters, QUARTERS_PER_YEAR)?;
+        Ok(DurationValue {
+            years,
+            quarters: quarters % QUARTERS_PER_YEAR,
+            months: self.months % MONTHS_PER_QUARTER,
+            weeks,
+            days: days % DAYS_PER_WEEK,
+            hours: hours % HOURS_PER_DAY,
+            minutes: minutes % MINUTES_PER_HOUR,
+            seconds: self.seconds % SECONDS_PER_MINUTE,
+            precision: self.precision,
+        })
+    }
+
+    /// Adds two durations unit by unit, the sum being exact only when both durations are exact
+    pub fn checked_add(&self, other: &DurationValue) -> Result<DurationValue> {
+        let add = |a: i64, b: i64| a.checked_add(b).ok_or_else(|| self.overflow());
+        Ok(DurationValue {
+            years: add(self.years, other.years)?,
+            quarters: add(self.quarters, other.quarters)?,
+            months: add(self.months, other.months)?,
+            weeks: add(self.weeks, other.weeks)?,
+            days: add(self.days, other.days)?,
+            hours: add(self.hours, other.hours)?,
+            minutes: add(self.minutes, other.minutes)?,
+            seconds: add(self.seconds, other.seconds)?,
+            precision: self.precision.combine(other.precision),
+        })
+    }
+
+    /// Returns the total number of months of the calendar units
+    fn total_months(&self) -> Result<i64> {
+        self.years
+            .checked_mul(QUARTERS_PER_YEAR)
+            .and_then(|quarters| quarters.checked_add(self.quarters))
+            .and_then(|quarters| quarters.checked_mul(MONTHS_PER_QUARTER))
+            .and_then(|months| months.checked_add(self.months))
+            .ok_or_else(|| self.overflow())
+    }
+
+    /// Returns the number of seconds of the duration, which is only possible when it does not
+    /// depend on a reference date, that is when it has no calendar units
+    pub fn fixed_seconds(&self) -> Result<DurationSeconds> {
+        if self.has_calendar_units() {
+            return Err(OntologyError::conversion_failure(
+                "Cannot compute the number of seconds of a duration with calendar units",
+            ));
+        }
+        let seconds = [
+            (self.weeks, DAYS_PER_WEEK),
+            (self.days, HOURS_PER_DAY),
+            (self.hours, MINUTES_PER_HOUR),
+            (self.minutes, SECONDS_PER_MINUTE),
+        ]
+        .iter()
+        .try_fold(0_i64, |total, (value, factor)| {
+            total.checked_add(*value)?.checked_mul(*factor)
+        })
+        .and_then(|total| total.checked_add(self.seconds))
+        .ok_or_else(|| self.overflow())?;
+        Ok(DurationSeconds {
+            seconds,
+            precision: self.precision,
+        })
+    }
+
+    /// Returns the number of seconds elapsed when applying the duration from `reference`
+    ///
+    /// Calendar units are applied first, so that one month starting on January 31st ends on the
+    /// last day of February.
+    #[cfg(feature = "chrono")]
+    pub fn total_seconds(&self, reference: &DateTime<FixedOffset>) -> Result<DurationSeconds> {
+        let out_of_range = || {
+            OntologyError::conversion_failure(format!(
+                "Duration out of range when applied to {}",
+                reference
+            ))
+        };
+        let months = self.total_months()?;
+        let month_count =
+            Months::new(u32::try_from(months.unsigned_abs()).map_err(|_| out_of_range())?);
+        let shifted = if months >= 0 {
+            reference.checked_add_months(month_count)
+        } else {
+            reference.checked_sub_months(month_count)
+        }
+        .ok_or_else(out_of_range)?;
+        let fixed_seconds = DurationValue {
+            years: 0,
+            quarters: 0,
+            months: 0,
+            ..self.clone()
+        }
+        .fixed_seconds()?;
+        let end = Duration::try_seconds(fixed_seconds.seconds)
+            .and_then(|fixed_duration| shifted.checked_add_signed(fixed_duration))
+            .ok_or_else(out_of_range)?;
+        Ok(DurationSeconds {
+            seconds: end.signed_duration_since(*reference).num_seconds(),
+            precision: self.precision,
+        })
+    }
+
+    /// Compares two durations when both are applied from `reference`
+    ///
+    /// An `Ordering` cannot express precision: approximate durations are compared by their
+    /// nominal values, and the ordering is only reliable when the combined precision of both
+    /// durations is exact.
+    #[cfg(feature = "chrono")]
+    pub fn cmp_at(
+        &self,
+        other: &DurationValue,
+        reference: &DateTime<FixedOffset>,
+    ) -> Result<Ordering> {
+        Ok(self
+            .total_seconds(reference)?
+            .seconds
+            .cmp(&other.total_seconds(reference)?.seconds))
+    }
+
+    /// Converts the duration into a `std::time::Duration`, which is only possible when it has
+    /// no calendar units and is not negative
+    ///
+    /// `std::time::Duration` has no notion of precision, so approximate durations are converted
+    /// by their nominal value.
+    pub fn to_std_duration(&self) -> Result<::std::time::Duration> {
+        let seconds = self.fixed_seconds()?.seconds;
+        if seconds < 0 {
+            return Err(OntologyError::conversion_failure(
+                "Cannot convert a negative duration to a std::time::Duration",
+            ));
+        }
+        Ok(::std::time::Duration::from_secs(seconds as u64))
+    }
+
+    /// Builds a normalized exact duration from a `std::time::Duration`, which is only possible
+    /// when it has no sub-second part
+    pub fn from_std_duration(duration: ::std::time::Duration) -> Result<DurationValue> {
+        if duration.subsec_nanos() != 0 {
+            return Err(OntologyError::conversion_failure(format!(
+                "Cannot represent a sub-second duration: {:?}",
+                duration
+            )));
+        }
+        let seconds = duration.as_secs();
+        if seconds > i64::MAX as u64 {
+            return Err(OntologyError::conversion_failure(format!(
+                "Duration is too large: {:?}",
+                duration
+            )));
+        }
+        DurationValue {
+            seconds: seconds as i64,
+            ..DurationValue::zero()
+        }
+        .normalize()
+    }
+
+    /// Formats the duration as an ISO 8601 duration string, e.g. "P3M" or "PT1H30M"
+    ///
+    /// Quarters are expressed as months since ISO 8601 has no quarter designator, and a duration
+    /// whose units are all negative is prefixed with a minus sign. Durations mixing positive and
+    /// negative units cannot be formatted.
+    pub fn to_iso8601(&self) -> Result<String> {
+        let months = DurationValue {
+            years: 0,
+            ..self.clone()
+        }
+        .total_months()?;
+        let date_units = [
+            (self.years, 'Y'),
+            (months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ];
+        let time_units = [(self.hours, 'H'), (self.minutes, 'M'), (self.seconds, 'S')];
+        let all_units = || date_units.iter().chain(time_units.iter());
+        let negative = all_units().any(|(value, _)| *value < 0);
+        if negative && all_units().any(|(value, _)| *value > 0) {
+            return Err(OntologyError::conversion_failure(format!(
+                "Cannot format a duration mixing positive and negative units: {:?}",
+                self
+            )));
+        }
+        let mut formatted = if negative { "-P" } else { "P" }.to_string();
+        if all_units().all(|(value, _)| *value == 0) {
+            formatted.push_str("T0S");
+            return Ok(formatted);
+        }
+        for (value, designator) in date_units.iter().filter(|(value, _)| *value != 0) {
+            formatted.push_str(&format!("{}{}", value.unsigned_abs(), designator));
+        }
+        if time_units.iter().any(|(value, _)| *value != 0) {
+            formatted.push('T');
+            for (value, designator) in time_units.iter().filter(|(value, _)| *value != 0) {
+                formatted.push_str(&format!("{}{}", value.unsigned_abs(), designator));
+            }
+        }
+        Ok(formatted)
+    }
+
+    /// Parses an ISO 8601 duration string such as "P3M" or "-PT1H30M" into an exact duration
+    ///
+    /// Only integer values are supported.
+    pub fn from_iso8601(value: &str) -> Result<DurationValue> {
+        let invalid =
+            || OntologyError::conversion_failure(format!("Invalid ISO 8601 duration: '{}'", value));
+        let (sign, unsigned) = if let Some(unsigned) = value.strip_prefix('-') {
+            (-1, unsigned)
+        } else {
+            (1, value.strip_prefix('+').unwrap_or(value))
+        };
+        if !unsigned.starts_with('P') || unsigned.len() < 2 || unsigned.ends_with('T') {
+            return Err(invalid());
+        }
+        let mut duration = DurationValue::zero();
+        let mut in_time_part = false;
+        let mut number = String::new();
+        // Designators must appear at most once and in order, which their rank enforces
+        let mut last_rank = None;
+        for c in unsigned[1..].chars() {
+            let (rank, field) = match (c, in_time_part) {
+                ('0'..='9', _) => {
+                    number.push(c);
+                    continue;
+                }
+                ('T', false) if number.is_empty() => {
+                    in_time_part = true;
+                    continue;
+                }
+                ('Y', false) => (0, &mut duration.years),
+                ('M', false) => (1, &mut duration.months),
+                ('W', false) => (2, &mut duration.weeks),
+                ('D', false) => (3, &mut duration.days),
+                ('H', true) => (4, &mut duration.hours),
+                ('M', true) => (5, &mut duration.minutes),
+                ('S', true) => (6, &mut duration.seconds),
+                _ => return Err(invalid()),
+            };
+            if number.is_empty() || last_rank >= Some(rank) {
+                return Err(invalid());
+            }
+            last_rank = Some(rank);
+            *field = sign * number.parse::<i64>().map_err(|_| invalid())?;
+            number.clear();
+        }
+        if !number.is_empty() {
+            return Err(invalid());
+        }
+        Ok(duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::too_many_arguments)]
+    fn duration(
+        years: i64,
+        quarters: i64,
+        months: i64,
+        weeks: i64,
+        days: i64,
+        hours: i64,
+        minutes: i64,
+        seconds: i64,
+    ) -> DurationValue {
+        DurationValue {
+            years,
+            quarters,
+            months,
+            weeks,
+            days,
+            hours,
+            minutes,
+            seconds,
+            precision: Precision::Exact,
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        // Given
+        let ninety_minutes = duration(0, 0, 0, 0, 0, 0, 90, 0);
+        let large = DurationValue {
+            precision: Precision::Approximate,
+            ..duration(0, 5, 14, 0, 15, 25, 61, 3661)
+        };
+
+        // When/Then
+        assert_eq!(
+            duration(0, 0, 0, 0, 0, 1, 30, 0),
+            ninety_minutes.normalize().unwrap()
+        );
+        assert_eq!(
+            DurationValue {
+                precision: Precision::Approximate,
+                ..duration(2, 1, 2, 2, 2, 3, 2, 1)
+            },
+            large.normalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        // Given
+        let first = duration(0, 0, 1, 0, 0, 0, 45, 0);
+        let second = DurationValue {
+            precision: Precision::Approximate,
+            ..duration(1, 0, 0, 0, 0, 0, 45, 0)
+        };
+
+        // When
+        let sum = first.checked_add(&second).unwrap();
+
+        // Then
+        assert_eq!(
+            DurationValue {
+                precision: Precision::Approximate,
+                ..duration(1, 0, 1, 0, 0, 1, 30, 0)
+            },
+            sum.normalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fixed_seconds() {
+        // Given
+        let exact = duration(0, 0, 0, 1, 1, 1, 1, 1);
+        let approximate = DurationValue {
+            precision: Precision::Approximate,
+            ..exact.clone()
+        };
+
+        // When/Then
+        assert_eq!(
+            DurationSeconds {
+                seconds: 694_861,
+                precision: Precision::Exact,
+            },
+            exact.fixed_seconds().unwrap()
+        );
+        assert_eq!(
+            DurationSeconds {
+                seconds: 694_861,
+                precision: Precision::Approximate,
+            },
+            approximate.fixed_seconds().unwrap()
+        );
+        assert!(duration(0, 0, 1, 0, 0, 0, 0, 0).fixed_seconds().is_err());
+    }
+
+    #[test]
+    fn test_overflows_are_errors() {
+        // Given
+        let max_weeks = duration(0, 0, 0, i64::MAX, 0, 0, 0, 0);
+        let max_minutes = duration(0, 0, 0, 0, 0, 0, i64::MAX, 60);
+        let max_quarters = duration(0, i64::MAX, 0, 0, 0, 0, 0, 0);
+
+        // When/Then
+        assert!(max_weeks.fixed_seconds().is_err());
+        assert!(max_weeks.to_std_duration().is_err());
+        assert!(max_minutes.normalize().is_err());
+        assert!(max_weeks.checked_add(&max_weeks).is_err());
+        assert!(max_quarters.to_iso8601().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_total_seconds() {
+        // Given
+        let january = DateTime::parse_from_rfc3339("2019-01-31T10:00:00+01:00").unwrap();
+        let march = DateTime::parse_from_rfc3339("2019-03-01T10:00:00+01:00").unwrap();
+        let one_month = duration(0, 0, 1, 0, 0, 0, 0, 0);
+        let thirty_days = duration(0, 0, 0, 0, 30, 0, 0, 0);
+
+        // When/Then
+        assert_eq!(
+            28 * 86_400,
+            one_month.total_seconds(&january).unwrap().seconds
+        );
+        assert_eq!(
+            31 * 86_400,
+            one_month.total_seconds(&march).unwrap().seconds
+        );
+        assert_eq!(
+            Ordering::Less,
+            one_month.cmp_at(&thirty_days, &january).unwrap()
+        );
+        assert_eq!(
+            Ordering::Greater,
+            one_month.cmp_at(&thirty_days, &march).unwrap()
+        );
+        assert_eq!(
+            366 * 86_400 + 3600,
+            duration(1, 0, 0, 0, 0, 1, 0, 0)
+                .total_seconds(&DateTime::parse_from_rfc3339("2020-01-01T00:00:00Z").unwrap())
+                .unwrap()
+                .seconds
+        );
+        assert_eq!(
+            Precision::Approximate,
+            DurationValue {
+                precision: Precision::Approximate,
+                ..one_month.clone()
+            }
+            .total_seconds(&january)
+            .unwrap()
+            .precision
+        );
+        assert!(duration(0, 0, i64::MAX, 0, 0, 0, 0, 0)
+            .total_seconds(&january)
+            .is_err());
+        assert!(duration(0, 0, 0, 0, 0, 0, 0, i64::MAX)
+            .total_seconds(&january)
+            .is_err());
+    }
+
+    #[test]
+    fn test_std_duration_conversions() {
+        // Given
+        let std_duration = ::std::time::Duration::from_secs(5400);
+
+        // When
+        let converted = DurationValue::from_std_duration(std_duration).unwrap();
+
+        // Then
+        assert_eq!(duration(0, 0, 0, 0, 0, 1, 30, 0), converted);
+        assert_eq!(std_duration, converted.to_std_duration().unwrap());
+        assert!(
+            DurationValue::from_std_duration(::std::time::Duration::from_millis(1500)).is_err()
+        );
+        assert!(duration(0, 0, 3, 0, 0, 0, 0, 0).to_std_duration().is_err());
+        assert!(duration(0, 0, 0, 0, 0, 0, -1, 0).to_std_duration().is_err());
+    }
+
+    #[test]
+    fn test_to_iso8601() {
+        assert_eq!(
+            "P3M",
+            duration(0, 0, 3, 0, 0, 0, 0, 0).to_iso8601().unwrap()
+        );
+        assert_eq!(
+            "P1Y5M2W3DT4H5M6S",
+            duration(1, 1, 2, 2, 3, 4, 5, 6).to_iso8601().unwrap()
+        );
+        assert_eq!(
+            "PT1H30M",
+            duration(0, 0, 0, 0, 0, 1, 30, 0).to_iso8601().unwrap()
+        );
+        assert_eq!(
+            "-PT30M",
+            duration(0, 0, 0, 0, 0, 0, -30, 0).to_iso8601().unwrap()
+        );
+        assert_eq!("PT0S", DurationValue::zero().to_iso8601().unwrap());
+        assert_eq!(
+            "PT0S",
+            duration(0, 1, -3, 0, 0, 0, 0, 0).to_iso8601().unwrap()
+        );
+        assert!(duration(0, 0, 0, 0, 0, 1, -30, 0).to_iso8601().is_err());
+    }
+
+    #[test]
+    fn test_from_iso8601() {
+        assert_eq!(
+            duration(0, 0, 3, 0, 0, 0, 0, 0),
+            DurationValue::from_iso8601("P3M").unwrap()
+        );
+        assert_eq!(
+            duration(1, 0, 2, 2, 3, 4, 5, 6),
+            DurationValue::from_iso8601("P1Y2M2W3DT4H5M6S").unwrap()
+        );
+        assert_eq!(
+            duration(0, 0, 0, 0, 0, -1, -30, 0),
+            DurationValue::from_iso8601("-PT1H30M").unwrap()
+        );
+        for invalid in &[
+            "", "P", "PT", "3M", "P1.5D", "PT1D", "P1H", "P1M1M", "P0M1M", "P1D1Y", "PT1S1M",
+            "PT0H0H", "P1", "PTM",
+        ] {
+            assert!(DurationValue::from_iso8601(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_iso8601_round_trip() {
+        let value = duration(1, 0, 2, 1, 3, 4, 5, 6);
+        assert_eq!(
+            value,
+            DurationValue::from_iso8601(&value.to_iso8601().unwrap()).unwrap()
+        );
+    }
+}
@@ -1,32 +1,65 @@
+use crate::entity::examples;
 use crate::enum_kind;
 use crate::errors::*;
+use crate::language::Language;
+use crate::migration::{FORMAT_VERSION, FORMAT_VERSION_KEY};
 use crate::ontology::*;
-use failure::format_err;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::Deserialize;
 use serde_json;
 use std::ops::Range;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// Builtin entity found in an input
+///
+/// Its JSON representation carries a `formatVersion` marker, see the `migration` module.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "json_schema",
+    schemars(transform = crate::json_schema::add_format_version_property)
+)]
 pub struct BuiltinEntity {
     pub value: String,
+    /// Range of the value in the input, expressed in Unicode scalar values
     pub range: Range<usize>,
     pub entity: SlotValue,
     pub alternatives: Vec<SlotValue>,
-    #[serde(
-        serialize_with = "serialize_builtin_entity_kind",
-        deserialize_with = "deserialize_builtin_entity_kind"
+    #[serde(deserialize_with = "deserialize_builtin_entity_kind")]
+    #[cfg_attr(
+        feature = "json_schema",
+        schemars(schema_with = "builtin_entity_kind_identifier_schema")
     )]
     pub entity_kind: BuiltinEntityKind,
 }
 
-fn serialize_builtin_entity_kind<S>(
-    value: &BuiltinEntityKind,
-    serializer: S,
-) -> ::std::result::Result<S::Ok, S::Error>
-where
-    S: ::serde::Serializer,
-{
-    serializer.serialize_str(value.identifier())
+#[cfg(feature = "json_schema")]
+fn builtin_entity_kind_identifier_schema(
+    _generator: &mut schemars::SchemaGenerator,
+) -> schemars::Schema {
+    let identifiers = BuiltinEntityKind::all()
+        .iter()
+        .map(|kind| kind.identifier())
+        .collect::<Vec<_>>();
+    schemars::json_schema!({
+        "type": "string",
+        "enum": identifiers,
+    })
+}
+
+impl Serialize for BuiltinEntity {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("BuiltinEntity", 6)?;
+        state.serialize_field(FORMAT_VERSION_KEY, &FORMAT_VERSION)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("range", &self.range)?;
+        state.serialize_field("entity", &self.entity)?;
+        state.serialize_field("alternatives", &self.alternatives)?;
+        state.serialize_field("entity_kind", self.entity_kind.identifier())?;
+        state.end()
+    }
 }
 
 fn deserialize_builtin_entity_kind<'de, D>(
@@ -76,6 +109,14 @@ pub trait IntoBuiltinEntityKind: Copy {
     fn result_description(&self) -> String {
         self.into_builtin_kind().result_description()
     }
+
+    fn supported_languages(&self) -> &'static [Language] {
+        self.into_builtin_kind().supported_languages()
+    }
+
+    fn examples(&self, language: Language) -> &'static [&'static str] {
+        self.into_builtin_kind().examples(language)
+    }
 }
 
 impl BuiltinEntityKind {
@@ -106,7 +147,10 @@ impl BuiltinEntityKind {
             .iter()
             .find(|kind| kind.identifier() == identifier)
             .map(|k| k.clone())
-            .ok_or(format_err!("Unknown EntityKind identifier: {}", identifier))
+            .ok_or_else(|| OntologyError::UnknownEntityKind {
+                kind: "BuiltinEntityKind",
+                value: identifier.to_string(),
+            })
     }
 }
 
@@ -140,6 +184,61 @@ impl BuiltinEntityKind {
     }
 }
 
+impl BuiltinEntityKind {
+    pub fn supported_languages(&self) -> &'static [Language] {
+        const ALL_LANGUAGES: &[Language] = &[
+            Language::DE,
+            Language::EN,
+            Language::ES,
+            Language::FR,
+            Language::IT,
+            Language::PT_PT,
+            Language::PT_BR,
+            Language::JA,
+            Language::KO,
+        ];
+        const ALL_LANGUAGES_BUT_KOREAN: &[Language] = &[
+            Language::DE,
+            Language::EN,
+            Language::ES,
+            Language::FR,
+            Language::IT,
+            Language::PT_PT,
+            Language::PT_BR,
+            Language::JA,
+        ];
+        match *self {
+            BuiltinEntityKind::AmountOfMoney => ALL_LANGUAGES,
+            BuiltinEntityKind::Duration => ALL_LANGUAGES,
+            BuiltinEntityKind::Number => ALL_LANGUAGES,
+            BuiltinEntityKind::Ordinal => ALL_LANGUAGES,
+            BuiltinEntityKind::Temperature => ALL_LANGUAGES,
+            BuiltinEntityKind::Datetime => ALL_LANGUAGES,
+            BuiltinEntityKind::Date => ALL_LANGUAGES,
+            BuiltinEntityKind::Time => ALL_LANGUAGES,
+            BuiltinEntityKind::DatePeriod => ALL_LANGUAGES,
+            BuiltinEntityKind::TimePeriod => ALL_LANGUAGES,
+            BuiltinEntityKind::Percentage => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::MusicAlbum => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::MusicArtist => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::MusicTrack => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::City => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::Country => ALL_LANGUAGES_BUT_KOREAN,
+            BuiltinEntityKind::Region => ALL_LANGUAGES_BUT_KOREAN,
+        }
+    }
+
+    pub fn is_supported(&self, language: Language) -> bool {
+        self.supported_languages().contains(&language)
+    }
+
+    /// Example utterances matched by the entity in the given language, which are empty when the
+    /// language is not supported
+    pub fn examples(&self, language: Language) -> &'static [&'static str] {
+        examples::examples(*self, language)
+    }
+}
+
 impl BuiltinEntityKind {
     pub fn result_description(&self) -> String {
         match *self {
@@ -259,6 +358,7 @@ impl BuiltinEntityKind {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entity::gazetteer_entity::BuiltinGazetteerEntityKind;
     use serde_test::{assert_tokens, Token};
 
     #[test]
@@ -272,6 +372,74 @@ mod tests {
         assert_eq!(expected_description, description);
     }
 
+    #[test]
+    fn test_supported_languages() {
+        // Given
+        let all_languages = Language::all();
+
+        // When
+        let number_languages = BuiltinEntityKind::Number.supported_languages();
+        let music_languages = BuiltinGazetteerEntityKind::MusicArtist.supported_languages();
+
+        // Then
+        assert_eq!(all_languages.len(), number_languages.len());
+        assert!(!music_languages.contains(&Language::KO));
+        assert!(BuiltinEntityKind::MusicAlbum.is_supported(Language::PT_BR));
+        for kind in BuiltinEntityKind::all() {
+            assert!(!kind.supported_languages().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_examples_are_defined_for_supported_languages() {
+        for kind in BuiltinEntityKind::all() {
+            for language in Language::all() {
+                assert_eq!(
+                    kind.is_supported(*language),
+                    !kind.examples(*language).is_empty(),
+                    "Inconsistent examples for {:?} in {:?}",
+                    kind,
+                    language
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_kind_conversion_errors() {
+        // Given
+        use crate::entity::grammar_entity::TryIntoGrammarEntityKind;
+        use std::str::FromStr;
+
+        // When
+        let unknown_identifier = BuiltinEntityKind::from_identifier("snips/unknown");
+        let unknown_name = BuiltinGazetteerEntityKind::from_str("Number");
+        let invalid_sub_kind = BuiltinEntityKind::City.try_into_grammar_kind();
+
+        // Then
+        assert_eq!(
+            Err(OntologyError::UnknownEntityKind {
+                kind: "BuiltinEntityKind",
+                value: "snips/unknown".to_string(),
+            }),
+            unknown_identifier
+        );
+        assert_eq!(
+            Err(OntologyError::UnknownEntityKind {
+                kind: "BuiltinGazetteerEntityKind",
+                value: "Number".to_string(),
+            }),
+            unknown_name
+        );
+        assert_eq!(
+            Err(OntologyError::InvalidSubKindConversion {
+                entity_kind: BuiltinEntityKind::City,
+                sub_kind: "GrammarEntityKind",
+            }),
+            invalid_sub_kind
+        );
+    }
+
     #[test]
     fn test_builtin_entity_ser_de() {
         let entity = BuiltinEntity {
@@ -295,8 +463,10 @@ mod tests {
             &[
                 Token::Struct {
                     name: "BuiltinEntity",
-                    len: 5,
+                    len: 6,
                 },
+                Token::Str("formatVersion"),
+                Token::U32(4),
                 Token::Str("value"),
                 Token::Str("hello"),
                 Token::Str("range"),
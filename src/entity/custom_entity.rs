@@ -0,0 +1,255 @@
+use crate::ontology::SlotValue;
+
+/// Entity defined by its values, as found in the training dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomEntity {
+    pub data: Vec<EntityValue>,
+    pub use_synonyms: bool,
+    /// Whether values which are not listed in `data` can be extracted
+    pub automatically_extensible: bool,
+    /// Strictness of the matching of entity values, between 0 (loose) and 1 (exact), i.e. the
+    /// minimal fraction of the tokens of a value which a raw value must contain to resolve to it
+    #[serde(default = "default_matching_strictness")]
+    pub matching_strictness: f32,
+}
+
+fn default_matching_strictness() -> f32 {
+    1.0
+}
+
+/// Canonical value of a custom entity along with its synonyms
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityValue {
+    pub value: String,
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+}
+
+/// How a raw value has been resolved to a canonical value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionKind {
+    /// The raw value is a value of the entity, or one of its synonyms
+    Exact,
+    /// The raw value matches a value or a synonym once both are normalized
+    Normalized,
+    /// The raw value contains enough tokens of a value or a synonym, according to the matching
+    /// strictness of the entity
+    Partial,
+    /// The raw value is not listed, but the entity is automatically extensible
+    Extension,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolution {
+    pub value: String,
+    pub kind: ResolutionKind,
+}
+
+/// Normalizes a value before its comparison with entity values, by lowercasing it and collapsing
+/// whitespaces
+pub fn normalize_entity_value(value: &str) -> String {
+    value
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Fraction of the tokens of the normalized `variant` which are found in `raw_tokens`
+fn token_overlap(variant: &str, raw_tokens: &[&str]) -> f32 {
+    let tokens = variant.split(' ').collect::<Vec<_>>();
+    let found = tokens
+        .iter()
+        .filter(|token| raw_tokens.contains(token))
+        .count();
+    found as f32 / tokens.len() as f32
+}
+
+impl CustomEntity {
+    /// Strings which resolve to the value, i.e. the value itself and its synonyms when they are
+    /// used
+    fn variants<'a>(&'a self, value: &'a EntityValue) -> impl Iterator<Item = &'a String> {
+        let synonyms = if self.use_synonyms {
+            &value.synonyms[..]
+        } else {
+            &[]
+        };
+        Some(&value.value).into_iter().chain(synonyms)
+    }
+
+    /// Resolves a raw value to its canonical value
+    ///
+    /// Exact matches take precedence over normalized ones, and values are looked up in the
+    /// order of `data`. When `matching_strictness` is lower than 1, raw values then resolve to
+    /// the value sharing the largest fraction of its tokens with them, as long as this fraction
+    /// reaches the strictness. Unlisted raw values are kept as is when the entity is
+    /// automatically extensible, and are not resolved otherwise.
+    pub fn resolve(&self, raw_value: &str) -> Option<Resolution> {
+        let find_value = |matches: &dyn Fn(&String) -> bool| {
+            self.data
+                .iter()
+                .find(|value| self.variants(value).any(matches))
+                .map(|value| value.value.clone())
+        };
+        if let Some(value) = find_value(&|variant| variant == raw_value) {
+            return Some(Resolution {
+                value,
+                kind: ResolutionKind::Exact,
+            });
+        }
+        let normalized_raw_value = normalize_entity_value(raw_value);
+        if let Some(value) =
+            find_value(&|variant| normalize_entity_value(variant) == normalized_raw_value)
+        {
+            return Some(Resolution {
+                value,
+                kind: ResolutionKind::Normalized,
+            });
+        }
+        if let Some(value) = self.find_partial_match(&normalized_raw_value) {
+            return Some(Resolution {
+                value,
+                kind: ResolutionKind::Partial,
+            });
+        }
+        if self.automatically_extensible {
+            Some(Resolution {
+                value: raw_value.to_string(),
+                kind: ResolutionKind::Extension,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn find_partial_match(&self, normalized_raw_value: &str) -> Option<String> {
+        if self.matching_strictness >= 1.0 || normalized_raw_value.is_empty() {
+            return None;
+        }
+        let raw_tokens = normalized_raw_value.split(' ').collect::<Vec<_>>();
+        let mut best_match = None;
+        let mut best_overlap = 0.0;
+        for value in &self.data {
+            let overlap = self
+                .variants(value)
+                .map(|variant| token_overlap(&normalize_entity_value(variant), &raw_tokens))
+                .fold(0.0, f32::max);
+            if overlap > best_overlap && overlap >= self.matching_strictness {
+                best_match = Some(value.value.clone());
+                best_overlap = overlap;
+            }
+        }
+        best_match
+    }
+
+    /// Resolves a raw value to the slot value that the entity yields
+    pub fn resolve_slot_value(&self, raw_value: &str) -> Option<SlotValue> {
+        self.resolve(raw_value)
+            .map(|resolution| SlotValue::Custom(resolution.value.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beverage_with_strictness(matching_strictness: f32) -> CustomEntity {
+        CustomEntity {
+            matching_strictness,
+            ..beverage(false, false)
+        }
+    }
+
+    fn beverage(use_synonyms: bool, automatically_extensible: bool) -> CustomEntity {
+        CustomEntity {
+            data: vec![
+                EntityValue {
+                    value: "Café au lait".to_string(),
+                    synonyms: vec!["latte".to_string()],
+                },
+                EntityValue {
+                    value: "tea".to_string(),
+                    synonyms: vec!["Café au Lait".to_string()],
+                },
+            ],
+            use_synonyms,
+            automatically_extensible,
+            matching_strictness: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        // Given
+        let entity = beverage(true, false);
+
+        // When/Then
+        assert_eq!(
+            Some(Resolution {
+                value: "Café au lait".to_string(),
+                kind: ResolutionKind::Exact,
+            }),
+            entity.resolve("latte")
+        );
+        assert_eq!(
+            Some(Resolution {
+                value: "tea".to_string(),
+                kind: ResolutionKind::Exact,
+            }),
+            entity.resolve("Café au Lait")
+        );
+        assert_eq!(
+            Some(Resolution {
+                value: "Café au lait".to_string(),
+                kind: ResolutionKind::Normalized,
+            }),
+            entity.resolve(" café  AU lait")
+        );
+        assert_eq!(None, entity.resolve("coffee"));
+    }
+
+    #[test]
+    fn test_resolve_without_synonyms() {
+        // Given
+        let entity = beverage(false, true);
+
+        // When/Then
+        assert_eq!(
+            Some(SlotValue::Custom("Café au lait".into())),
+            entity.resolve_slot_value("Café au Lait")
+        );
+        assert_eq!(
+            Some(Resolution {
+                value: "latte".to_string(),
+                kind: ResolutionKind::Extension,
+            }),
+            entity.resolve("latte")
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_matching_strictness() {
+        // Given
+        let strict_entity = beverage_with_strictness(1.0);
+        let loose_entity = beverage_with_strictness(0.6);
+
+        // When/Then
+        assert_eq!(None, strict_entity.resolve("café lait"));
+        assert_eq!(
+            Some(Resolution {
+                value: "Café au lait".to_string(),
+                kind: ResolutionKind::Partial,
+            }),
+            loose_entity.resolve("café lait")
+        );
+        assert_eq!(
+            Some(Resolution {
+                value: "tea".to_string(),
+                kind: ResolutionKind::Partial,
+            }),
+            loose_entity.resolve("Iced tea")
+        );
+        assert_eq!(None, loose_entity.resolve("lait"));
+        assert_eq!(None, beverage_with_strictness(0.0).resolve("coffee"));
+    }
+}
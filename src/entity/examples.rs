@@ -0,0 +1,350 @@
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use crate::language::Language;
+
+pub(crate) fn examples(
+    entity_kind: BuiltinEntityKind,
+    language: Language,
+) -> &'static [&'static str] {
+    match entity_kind {
+        BuiltinEntityKind::AmountOfMoney => amount_of_money_examples(language),
+        BuiltinEntityKind::Duration => duration_examples(language),
+        BuiltinEntityKind::Number => number_examples(language),
+        BuiltinEntityKind::Ordinal => ordinal_examples(language),
+        BuiltinEntityKind::Temperature => temperature_examples(language),
+        BuiltinEntityKind::Datetime => datetime_examples(language),
+        BuiltinEntityKind::Date => date_examples(language),
+        BuiltinEntityKind::Time => time_examples(language),
+        BuiltinEntityKind::DatePeriod => date_period_examples(language),
+        BuiltinEntityKind::TimePeriod => time_period_examples(language),
+        BuiltinEntityKind::Percentage => percentage_examples(language),
+        BuiltinEntityKind::MusicAlbum => music_album_examples(language),
+        BuiltinEntityKind::MusicArtist => music_artist_examples(language),
+        BuiltinEntityKind::MusicTrack => music_track_examples(language),
+        BuiltinEntityKind::City => city_examples(language),
+        BuiltinEntityKind::Country => country_examples(language),
+        BuiltinEntityKind::Region => region_examples(language),
+    }
+}
+
+fn amount_of_money_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["10$", "ungefähr 5€", "zwei tausend Dollar"],
+        Language::EN => &[
+            "$10",
+            "six euros",
+            "around 5€",
+            "ten dollars and five cents",
+        ],
+        Language::ES => &["10$", "aproximadamente 5€", "dos mil dólares"],
+        Language::FR => &["10$", "environ 5€", "deux mille dollars"],
+        Language::IT => &["10$", "circa 5€", "due mila dollari"],
+        Language::PT_PT => &["10$", "aproximadamente 5€", "dois mil dólares"],
+        Language::PT_BR => &["10$", "cerca de 5€", "dois mil dólares"],
+        Language::JA => &["8ドル", "五十二アメリカドル", "約5ユーロ"],
+        Language::KO => &["10달러", "약 5 유로", "10 달러 5 센트"],
+    }
+}
+
+fn duration_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &[
+            "2stdn",
+            "drei monate",
+            "ein halbe Stunde",
+            "8 Jahre und zwei Tage",
+        ],
+        Language::EN => &[
+            "1h",
+            "during two minutes",
+            "for 20 seconds",
+            "3 months",
+            "half an hour",
+        ],
+        Language::ES => &["2 horas", "durante tres minutos", "8 años y dos días"],
+        Language::FR => &["2 heures", "pendant trois minutes", "8 ans et deux jours"],
+        Language::IT => &["durante due ore", "tre minuti", "8 anni e due giorni"],
+        Language::PT_PT => &["2 horas", "durante três minutos", "8 anos e dois dias"],
+        Language::PT_BR => &["2 horas", "por três minutos", "8 anos e dois dias"],
+        Language::JA => &["一秒間", "五日間", "十ヶ月間"],
+        Language::KO => &["양일", "1분", "5시간"],
+    }
+}
+
+fn number_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["2001", "einundzwanzig", "dreihundertvier"],
+        Language::EN => &["2001", "twenty one", "three hundred and four"],
+        Language::ES => &["2001", "veintiuno", "trescientos cuatro"],
+        Language::FR => &["2001", "vingt et un", "trois cent quatre"],
+        Language::IT => &["2001", "ventuno", "trecentoquattro"],
+        Language::PT_PT => &["2001", "vinte e um", "trezentos e quatro"],
+        Language::PT_BR => &["2001", "vinte e um", "trezentos e quatro"],
+        Language::JA => &["十二", "二千五", "四千三百二"],
+        Language::KO => &["여덟", "삼십", "칠십사"],
+    }
+}
+
+fn ordinal_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["Erste", "der zweite", "Zwanzigster"],
+        Language::EN => &["1st", "the second", "the twenty third"],
+        Language::ES => &["primer", "segundo", "vigésimo primero"],
+        Language::FR => &["1er", "vingt-et-unieme", "quarante-deuxieme"],
+        Language::IT => &["primo", "secondo", "ventunesimo"],
+        Language::PT_PT => &["primeiro", "segundo", "vigésimo primeiro"],
+        Language::PT_BR => &["primeiro", "segundo", "vigésimo primeiro"],
+        Language::JA => &["一番目", "十番目", "九十八番目"],
+        Language::KO => &["첫번째", "두번째", "열번째"],
+    }
+}
+
+fn temperature_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &[
+            "70K",
+            "3°C",
+            "Dreiundzwanzig Grad",
+            "zweiunddreißig Grad Fahrenheit",
+        ],
+        Language::EN => &[
+            "70K",
+            "3°C",
+            "Twenty three degrees",
+            "one hundred degrees fahrenheit",
+        ],
+        Language::ES => &[
+            "70K",
+            "3°C",
+            "veintitrés grados",
+            "treinta y dos grados fahrenheit",
+        ],
+        Language::FR => &[
+            "70K",
+            "3°C",
+            "vingt-trois degrés",
+            "trente-deux degrés fahrenheit",
+        ],
+        Language::IT => &["70K", "3°C", "ventitré gradi", "trentadue gradi fahrenheit"],
+        Language::PT_PT => &[
+            "70K",
+            "3°C",
+            "vinte e três graus",
+            "trinta e dois graus fahrenheit",
+        ],
+        Language::PT_BR => &[
+            "70K",
+            "3°C",
+            "vinte e três graus",
+            "trinta e dois graus fahrenheit",
+        ],
+        Language::JA => &["摂氏二十三度", "華氏三十二度", "二十三度"],
+        Language::KO => &["섭씨 이십삼도", "화씨 삼십이도", "이십삼도"],
+    }
+}
+
+fn datetime_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &[
+            "Heute",
+            "um 8 Uhr",
+            "in einer Stunde",
+            "der dritte Dienstag im Juni",
+        ],
+        Language::EN => &[
+            "Today",
+            "at 8 a.m.",
+            "4:30 pm",
+            "in 1 hour",
+            "the 3rd tuesday of June",
+        ],
+        Language::ES => &[
+            "Hoy",
+            "a las 8 de la mañana",
+            "en una hora",
+            "el tercer martes de junio",
+        ],
+        Language::FR => &[
+            "Aujourd'hui",
+            "à 8 heures",
+            "dans une heure",
+            "le troisième mardi de juin",
+        ],
+        Language::IT => &["Oggi", "alle 8", "tra un'ora", "il terzo martedì di giugno"],
+        Language::PT_PT => &[
+            "Hoje",
+            "às oito da manhã",
+            "daqui a uma hora",
+            "a terceira terça-feira de junho",
+        ],
+        Language::PT_BR => &[
+            "Hoje",
+            "às oito da manhã",
+            "em uma hora",
+            "a terceira terça-feira de junho",
+        ],
+        Language::JA => &["今日", "午前八時", "一時間後", "六月の第三火曜日"],
+        Language::KO => &["오늘", "오전 8시", "한 시간 후", "6월 셋째 주 화요일"],
+    }
+}
+
+fn date_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["heute", "am Mittwoch", "am 26. März", "Samstag, 19. Januar"],
+        Language::EN => &["today", "on Wednesday", "March 26th", "saturday january 19"],
+        Language::ES => &[
+            "hoy",
+            "el miércoles",
+            "el 26 de marzo",
+            "sábado 19 de enero",
+        ],
+        Language::FR => &["aujourd'hui", "mercredi", "le 26 mars", "samedi 19 janvier"],
+        Language::IT => &["oggi", "mercoledì", "il 26 marzo", "sabato 19 gennaio"],
+        Language::PT_PT => &[
+            "hoje",
+            "na quarta-feira",
+            "a 26 de março",
+            "sábado 19 de janeiro",
+        ],
+        Language::PT_BR => &[
+            "hoje",
+            "na quarta-feira",
+            "dia 26 de março",
+            "sábado 19 de janeiro",
+        ],
+        Language::JA => &["今日", "水曜日", "三月二十六日"],
+        Language::KO => &["오늘", "수요일", "3월 26일"],
+    }
+}
+
+fn time_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["jetzt", "um Mittag", "um 8 Uhr morgens", "um 16:30"],
+        Language::EN => &["now", "at noon", "at 8 a.m.", "4:30 pm", "in one hour"],
+        Language::ES => &[
+            "ahora",
+            "al mediodía",
+            "a las 8 de la mañana",
+            "a las 16:30",
+        ],
+        Language::FR => &["maintenant", "à midi", "à 8h du matin", "à 16h30"],
+        Language::IT => &["adesso", "a mezzogiorno", "alle 8 di mattina", "alle 16:30"],
+        Language::PT_PT => &["agora", "ao meio-dia", "às 8 da manhã", "às 16:30"],
+        Language::PT_BR => &["agora", "ao meio-dia", "às 8 da manhã", "às 16:30"],
+        Language::JA => &["今", "正午", "午前八時", "午後四時半"],
+        Language::KO => &["지금", "정오", "오전 8시", "오후 4시 30분"],
+    }
+}
+
+fn date_period_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["Januar", "2019", "von Montag bis Freitag", "diese Woche"],
+        Language::EN => &["january", "2019", "from monday to friday", "this week"],
+        Language::ES => &["enero", "2019", "de lunes a viernes", "esta semana"],
+        Language::FR => &["janvier", "2019", "du lundi au vendredi", "cette semaine"],
+        Language::IT => &["gennaio", "2019", "da lunedì a venerdì", "questa settimana"],
+        Language::PT_PT => &["janeiro", "2019", "de segunda a sexta", "esta semana"],
+        Language::PT_BR => &["janeiro", "2019", "de segunda a sexta", "essa semana"],
+        Language::JA => &["一月", "二千十九年", "月曜日から金曜日まで", "今週"],
+        Language::KO => &["1월", "2019년", "월요일부터 금요일까지", "이번 주"],
+    }
+}
+
+fn time_period_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["bis zum Abendessen", "von fünf bis zehn Uhr", "heute Abend"],
+        Language::EN => &[
+            "until dinner",
+            "from five to ten",
+            "by the end of the day",
+            "tonight",
+        ],
+        Language::ES => &["hasta la cena", "de cinco a diez", "esta noche"],
+        Language::FR => &["jusqu'au dîner", "de cinq à dix heures", "ce soir"],
+        Language::IT => &["fino a cena", "dalle cinque alle dieci", "stasera"],
+        Language::PT_PT => &["até ao jantar", "das cinco às dez", "esta noite"],
+        Language::PT_BR => &["até o jantar", "das cinco às dez", "hoje à noite"],
+        Language::JA => &["夕食まで", "五時から十時まで", "今夜"],
+        Language::KO => &["저녁 식사까지", "5시부터 10시까지", "오늘 밤"],
+    }
+}
+
+fn percentage_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["25%", "zwanzig Prozent", "zweihundertfünfzig Prozent"],
+        Language::EN => &["25%", "twenty percent", "two hundred and fifty percents"],
+        Language::ES => &[
+            "25%",
+            "veinte por ciento",
+            "doscientos cincuenta por ciento",
+        ],
+        Language::FR => &["25%", "20 pourcents", "quatre-vingt dix pourcents"],
+        Language::IT => &["25%", "venti per cento", "duecentocinquanta per cento"],
+        Language::PT_PT => &["25%", "vinte por cento", "duzentos e cinquenta por cento"],
+        Language::PT_BR => &["25%", "vinte por cento", "duzentos e cinquenta por cento"],
+        Language::JA => &["25%", "五パーセント", "十五%"],
+        Language::KO => &[],
+    }
+}
+
+fn music_album_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::KO => &[],
+        _ => &["Discovery", "Abbey Road", "Kind of Blue"],
+    }
+}
+
+fn music_artist_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::KO => &[],
+        _ => &["Daft Punk", "Adele", "Bob Dylan"],
+    }
+}
+
+fn music_track_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::KO => &[],
+        _ => &["Harder Better Faster Stronger", "Yesterday", "Hello"],
+    }
+}
+
+fn city_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["Berlin", "München", "Paris"],
+        Language::EN => &["Paris", "London", "New York"],
+        Language::ES => &["Madrid", "Barcelona", "Nueva York"],
+        Language::FR => &["Paris", "Marseille", "Londres"],
+        Language::IT => &["Roma", "Milano", "Parigi"],
+        Language::PT_PT => &["Lisboa", "Porto", "Paris"],
+        Language::PT_BR => &["São Paulo", "Rio de Janeiro", "Brasília"],
+        Language::JA => &["東京", "大阪", "パリ"],
+        Language::KO => &[],
+    }
+}
+
+fn country_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["Deutschland", "Frankreich", "Vereinigte Staaten"],
+        Language::EN => &["France", "Italy", "United States"],
+        Language::ES => &["España", "Francia", "Estados Unidos"],
+        Language::FR => &["France", "Italie", "États-Unis"],
+        Language::IT => &["Italia", "Francia", "Stati Uniti"],
+        Language::PT_PT => &["Portugal", "França", "Estados Unidos"],
+        Language::PT_BR => &["Brasil", "França", "Estados Unidos"],
+        Language::JA => &["日本", "フランス", "アメリカ"],
+        Language::KO => &[],
+    }
+}
+
+fn region_examples(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::DE => &["Bayern", "Sachsen", "Kalifornien"],
+        Language::EN => &["California", "Texas", "Bavaria"],
+        Language::ES => &["Andalucía", "Cataluña", "California"],
+        Language::FR => &["Bretagne", "Île-de-France", "Californie"],
+        Language::IT => &["Toscana", "Lombardia", "Sicilia"],
+        Language::PT_PT => &["Algarve", "Alentejo", "Madeira"],
+        Language::PT_BR => &["São Paulo", "Minas Gerais", "Bahia"],
+        Language::JA => &["北海道", "沖縄県", "カリフォルニア州"],
+        Language::KO => &[],
+    }
+}
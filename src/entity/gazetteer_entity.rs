@@ -1,7 +1,6 @@
 use crate::entity::builtin_entity::{BuiltinEntityKind, IntoBuiltinEntityKind};
 use crate::errors::*;
 use crate::sub_entity_kind;
-use failure::format_err;
 
 sub_entity_kind!(
     BuiltinGazetteerEntityKind,
@@ -14,6 +13,11 @@ pub trait TryIntoBuiltinGazetteerEntityKind {
 
 impl TryIntoBuiltinGazetteerEntityKind for BuiltinEntityKind {
     fn try_into_gazetteer_kind(&self) -> Result<BuiltinGazetteerEntityKind> {
-        BuiltinGazetteerEntityKind::from_identifier(self.identifier())
+        BuiltinGazetteerEntityKind::from_identifier(self.identifier()).map_err(|_| {
+            OntologyError::InvalidSubKindConversion {
+                entity_kind: *self,
+                sub_kind: "BuiltinGazetteerEntityKind",
+            }
+        })
     }
 }
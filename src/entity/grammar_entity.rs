@@ -1,7 +1,6 @@
 use crate::entity::builtin_entity::{BuiltinEntityKind, IntoBuiltinEntityKind};
 use crate::errors::*;
 use crate::sub_entity_kind;
-use failure::format_err;
 
 sub_entity_kind!(
     GrammarEntityKind,
@@ -26,6 +25,11 @@ pub trait TryIntoGrammarEntityKind {
 
 impl TryIntoGrammarEntityKind for BuiltinEntityKind {
     fn try_into_grammar_kind(self) -> Result<GrammarEntityKind> {
-        GrammarEntityKind::from_identifier(self.identifier())
+        GrammarEntityKind::from_identifier(self.identifier()).map_err(|_| {
+            OntologyError::InvalidSubKindConversion {
+                entity_kind: self,
+                sub_kind: "GrammarEntityKind",
+            }
+        })
     }
 }
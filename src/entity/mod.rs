@@ -1,3 +1,5 @@
 pub mod builtin_entity;
+pub mod custom_entity;
+mod examples;
 pub mod gazetteer_entity;
 pub mod grammar_entity;
@@ -1 +1,61 @@
-pub type Result<T> = ::std::result::Result<T, ::failure::Error>;
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, OntologyError>;
+
+/// Error raised by the ontology
+#[derive(Debug, Clone, PartialEq)]
+pub enum OntologyError {
+    /// The string does not match any supported language
+    UnknownLanguage(String),
+    /// The string does not match any variant of the entity kind named `kind`
+    UnknownEntityKind { kind: &'static str, value: String },
+    /// The builtin entity kind has no counterpart in the sub-kind named `sub_kind`
+    InvalidSubKindConversion {
+        entity_kind: BuiltinEntityKind,
+        sub_kind: &'static str,
+    },
+    /// A value cannot be converted to or from another representation
+    ConversionFailure(String),
+    /// The lower bound of a time interval is after its upper bound
+    InvalidTimeInterval { from: String, to: String },
+}
+
+impl OntologyError {
+    pub(crate) fn conversion_failure<T: fmt::Display>(message: T) -> Self {
+        OntologyError::ConversionFailure(message.to_string())
+    }
+}
+
+impl fmt::Display for OntologyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OntologyError::UnknownLanguage(language) => write!(f, "Unknown language: {}", language),
+            OntologyError::UnknownEntityKind { kind, value } => {
+                write!(f, "{} is not a known {}", value, kind)
+            }
+            OntologyError::InvalidSubKindConversion {
+                entity_kind,
+                sub_kind,
+            } => write!(
+                f,
+                "{:?} cannot be converted into a {}",
+                entity_kind, sub_kind
+            ),
+            OntologyError::ConversionFailure(message) => write!(f, "{}", message),
+            OntologyError::InvalidTimeInterval { from, to } => write!(
+                f,
+                "Invalid time interval: lower bound {} is after upper bound {}",
+                from, to
+            ),
+        }
+    }
+}
+
+impl ::std::error::Error for OntologyError {}
+
+impl From<serde_json::Error> for OntologyError {
+    fn from(error: serde_json::Error) -> Self {
+        OntologyError::conversion_failure(error)
+    }
+}
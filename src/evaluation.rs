@@ -0,0 +1,483 @@
+//! Evaluation of predicted intent parsing results against gold annotations
+//!
+//! Intents are scored as a single-label classification, in which the null intent stands for the
+//! absence of intent: it has its own metrics like the other intents, but it is left out of the
+//! overall intent metrics, which are micro-averaged over the other intents.
+//!
+//! Slots are scored independently of the intent, a predicted slot matching at most one gold slot
+//! with the same slot name, and slots being paired so that as many gold slots as possible are
+//! matched. Exact matching requires the ranges to be equal, while partial matching
+//! only requires them to overlap. Slot metrics are also broken down by entity, the false positives
+//! being attributed to the entity of the predicted slot.
+
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use crate::ontology::{IntentParserResult, Slot};
+use crate::overlap::ranges_overlap;
+use std::collections::BTreeMap;
+
+/// Precision, recall and F1 score along with the counts they are computed from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metrics {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub precision: f32,
+    pub recall: f32,
+    pub f1: f32,
+}
+
+impl Metrics {
+    /// Computes the metrics from counts, undefined ratios being set to 0
+    pub fn new(true_positives: usize, false_positives: usize, false_negatives: usize) -> Self {
+        let ratio = |numerator: usize, denominator: usize| {
+            if denominator == 0 {
+                0.
+            } else {
+                numerator as f32 / denominator as f32
+            }
+        };
+        let precision = ratio(true_positives, true_positives + false_positives);
+        let recall = ratio(true_positives, true_positives + false_negatives);
+        let f1 = if precision + recall == 0. {
+            0.
+        } else {
+            2. * precision * recall / (precision + recall)
+        };
+        Metrics {
+            true_positives,
+            false_positives,
+            false_negatives,
+            precision,
+            recall,
+            f1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentMetrics {
+    /// Name of the intent, `None` being the null intent
+    pub intent_name: Option<String>,
+    pub metrics: Metrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityMetrics {
+    pub entity: String,
+    /// Kind of the entity when it is a builtin entity
+    pub builtin_entity_kind: Option<BuiltinEntityKind>,
+    pub exact_match: Metrics,
+    pub partial_match: Metrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvaluationReport {
+    pub intents: Vec<IntentMetrics>,
+    pub overall_intent: Metrics,
+    pub exact_match_slots: Metrics,
+    pub partial_match_slots: Metrics,
+    pub entities: Vec<EntityMetrics>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Counts {
+    true_positives: usize,
+    false_positives: usize,
+    false_negatives: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: Counts) {
+        self.true_positives += other.true_positives;
+        self.false_positives += other.false_positives;
+        self.false_negatives += other.false_negatives;
+    }
+
+    fn metrics(&self) -> Metrics {
+        Metrics::new(
+            self.true_positives,
+            self.false_positives,
+            self.false_negatives,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct SlotCounts {
+    exact_match: Counts,
+    partial_match: Counts,
+}
+
+/// Accumulates the comparisons of gold and predicted results
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Evaluation {
+    intents: BTreeMap<Option<String>, Counts>,
+    entities: BTreeMap<String, SlotCounts>,
+}
+
+/// Pairs gold slots with the predicted slots they match, so that as many gold slots as possible
+/// are paired, and returns the index of the predicted slot paired with each gold slot
+///
+/// Predicted slots with the same range as the gold slot are preferred, other pairings being found
+/// with augmenting paths.
+fn pair_slots<F>(gold_slots: &[Slot], predicted_slots: &[Slot], matches: F) -> Vec<Option<usize>>
+where
+    F: Fn(&Slot, &Slot) -> bool,
+{
+    let candidates = gold_slots
+        .iter()
+        .map(|gold_slot| {
+            let mut indices = (0..predicted_slots.len())
+                .filter(|index| matches(gold_slot, &predicted_slots[*index]))
+                .collect::<Vec<_>>();
+            indices.sort_by_key(|index| predicted_slots[*index].range != gold_slot.range);
+            indices
+        })
+        .collect::<Vec<_>>();
+    let mut gold_indices = vec![None; predicted_slots.len()];
+    for gold_index in 0..gold_slots.len() {
+        let mut visited = vec![false; predicted_slots.len()];
+        pair_gold_slot(gold_index, &candidates, &mut gold_indices, &mut visited);
+    }
+    let mut predicted_indices = vec![None; gold_slots.len()];
+    for (predicted_index, gold_index) in gold_indices.into_iter().enumerate() {
+        if let Some(gold_index) = gold_index {
+            predicted_indices[gold_index] = Some(predicted_index);
+        }
+    }
+    predicted_indices
+}
+
+/// Pairs the gold slot with one of its candidates, possibly pairing the gold slot previously
+/// paired with this candidate with another one, and returns whether it succeeded
+fn pair_gold_slot(
+    gold_index: usize,
+    candidates: &[Vec<usize>],
+    gold_indices: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &predicted_index in &candidates[gold_index] {
+        if visited[predicted_index] {
+            continue;
+        }
+        visited[predicted_index] = true;
+        let is_available = match gold_indices[predicted_index] {
+            Some(other_gold_index) => {
+                pair_gold_slot(other_gold_index, candidates, gold_indices, visited)
+            }
+            None => true,
+        };
+        if is_available {
+            gold_indices[predicted_index] = Some(gold_index);
+            return true;
+        }
+    }
+    false
+}
+
+impl Evaluation {
+    pub fn add(&mut self, gold: &IntentParserResult, predicted: &IntentParserResult) {
+        let gold_intent = &gold.intent.intent_name;
+        let predicted_intent = &predicted.intent.intent_name;
+        if gold_intent == predicted_intent {
+            self.intents
+                .entry(gold_intent.clone())
+                .or_default()
+                .true_positives += 1;
+        } else {
+            self.intents
+                .entry(gold_intent.clone())
+                .or_default()
+                .false_negatives += 1;
+            self.intents
+                .entry(predicted_intent.clone())
+                .or_default()
+                .false_positives += 1;
+        }
+        self.add_slots(&gold.slots, &predicted.slots, true);
+        self.add_slots(&gold.slots, &predicted.slots, false);
+    }
+
+    fn add_slots(&mut self, gold_slots: &[Slot], predicted_slots: &[Slot], exact_match: bool) {
+        let matches = |gold_slot: &Slot, predicted_slot: &Slot| {
+            gold_slot.slot_name == predicted_slot.slot_name
+                && if exact_match {
+                    gold_slot.range == predicted_slot.range
+                } else {
+                    ranges_overlap(&gold_slot.range, &predicted_slot.range)
+                }
+        };
+        let mut matched = vec![false; predicted_slots.len()];
+        let predicted_indices = pair_slots(gold_slots, predicted_slots, matches);
+        for (gold_slot, predicted_index) in gold_slots.iter().zip(predicted_indices) {
+            let counts = self.slot_counts(&gold_slot.entity, exact_match);
+            match predicted_index {
+                Some(index) => {
+                    matched[index] = true;
+                    counts.true_positives += 1;
+                }
+                None => counts.false_negatives += 1,
+            }
+        }
+        for (predicted_slot, _) in predicted_slots
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+        {
+            self.slot_counts(&predicted_slot.entity, exact_match)
+                .false_positives += 1;
+        }
+    }
+
+    fn slot_counts(&mut self, entity: &str, exact_match: bool) -> &mut Counts {
+        let counts = self.entities.entry(entity.to_string()).or_default();
+        if exact_match {
+            &mut counts.exact_match
+        } else {
+            &mut counts.partial_match
+        }
+    }
+
+    pub fn report(&self) -> EvaluationReport {
+        // The null intent is the absence of intent, hence it is left out of the overall metrics:
+        // misclassifications involving it are only counted once, for the other intent
+        let mut overall_intent = Counts::default();
+        for (_, counts) in self
+            .intents
+            .iter()
+            .filter(|(intent_name, _)| intent_name.is_some())
+        {
+            overall_intent.add(*counts);
+        }
+        let mut exact_match_slots = Counts::default();
+        let mut partial_match_slots = Counts::default();
+        for counts in self.entities.values() {
+            exact_match_slots.add(counts.exact_match);
+            partial_match_slots.add(counts.partial_match);
+        }
+        EvaluationReport {
+            intents: self
+                .intents
+                .iter()
+                .map(|(intent_name, counts)| IntentMetrics {
+                    intent_name: intent_name.clone(),
+                    metrics: counts.metrics(),
+                })
+                .collect(),
+            overall_intent: overall_intent.metrics(),
+            exact_match_slots: exact_match_slots.metrics(),
+            partial_match_slots: partial_match_slots.metrics(),
+            entities: self
+                .entities
+                .iter()
+                .map(|(entity, counts)| EntityMetrics {
+                    entity: entity.clone(),
+                    builtin_entity_kind: BuiltinEntityKind::from_identifier(entity).ok(),
+                    exact_match: counts.exact_match.metrics(),
+                    partial_match: counts.partial_match.metrics(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Evaluates pairs of gold and predicted results
+pub fn evaluate<'a, I>(results: I) -> EvaluationReport
+where
+    I: IntoIterator<Item = (&'a IntentParserResult, &'a IntentParserResult)>,
+{
+    let mut evaluation = Evaluation::default();
+    for (gold, predicted) in results {
+        evaluation.add(gold, predicted);
+    }
+    evaluation.report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::*;
+    use crate::test_utils::slot;
+    use serde_json::json;
+
+    fn result(intent_name: Option<&str>, slots: Vec<Slot>) -> IntentParserResult {
+        IntentParserResult {
+            input: "make me two cups of coffee".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: intent_name.map(|name| name.to_string()),
+                confidence_score: 0.8,
+            },
+            slots,
+            alternatives: vec![],
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        // When
+        let metrics = Metrics::new(3, 1, 2);
+        let undefined_metrics = Metrics::new(0, 0, 0);
+
+        // Then
+        assert_eq!(0.75, metrics.precision);
+        assert_eq!(0.6, metrics.recall);
+        assert!((metrics.f1 - 2. / 3.).abs() < 1e-6);
+        assert_eq!(0., undefined_metrics.f1);
+    }
+
+    #[test]
+    fn test_intent_metrics() {
+        // Given
+        let gold = [
+            result(Some("makeCoffee"), vec![]),
+            result(Some("makeCoffee"), vec![]),
+            result(Some("makeTea"), vec![]),
+            result(None, vec![]),
+            result(None, vec![]),
+        ];
+        let predicted = [
+            result(Some("makeCoffee"), vec![]),
+            result(Some("makeTea"), vec![]),
+            result(None, vec![]),
+            result(Some("makeCoffee"), vec![]),
+            result(None, vec![]),
+        ];
+
+        // When
+        let report = evaluate(gold.iter().zip(predicted.iter()));
+
+        // Then
+        let expected_intents = vec![
+            IntentMetrics {
+                intent_name: None,
+                metrics: Metrics::new(1, 1, 1),
+            },
+            IntentMetrics {
+                intent_name: Some("makeCoffee".to_string()),
+                metrics: Metrics::new(1, 1, 1),
+            },
+            IntentMetrics {
+                intent_name: Some("makeTea".to_string()),
+                metrics: Metrics::new(0, 1, 1),
+            },
+        ];
+        assert_eq!(expected_intents, report.intents);
+        assert_eq!(Metrics::new(1, 2, 2), report.overall_intent);
+    }
+
+    #[test]
+    fn test_slot_metrics() {
+        // Given
+        let gold = result(
+            Some("makeCoffee"),
+            vec![
+                slot("number_of_cups", "snips/number", 8..11),
+                slot("beverage", "beverage", 20..26),
+                slot("beverage", "beverage", 0..4),
+            ],
+        );
+        let predicted = result(
+            Some("makeCoffee"),
+            vec![
+                slot("number_of_cups", "snips/number", 8..11),
+                slot("beverage", "beverage", 19..26),
+                slot("size", "size", 12..16),
+            ],
+        );
+
+        // When
+        let report = evaluate([(&gold, &predicted)]);
+
+        // Then
+        assert_eq!(Metrics::new(1, 2, 2), report.exact_match_slots);
+        assert_eq!(Metrics::new(2, 1, 1), report.partial_match_slots);
+        let expected_entities = vec![
+            EntityMetrics {
+                entity: "beverage".to_string(),
+                builtin_entity_kind: None,
+                exact_match: Metrics::new(0, 1, 2),
+                partial_match: Metrics::new(1, 0, 1),
+            },
+            EntityMetrics {
+                entity: "size".to_string(),
+                builtin_entity_kind: None,
+                exact_match: Metrics::new(0, 1, 0),
+                partial_match: Metrics::new(0, 1, 0),
+            },
+            EntityMetrics {
+                entity: "snips/number".to_string(),
+                builtin_entity_kind: Some(BuiltinEntityKind::Number),
+                exact_match: Metrics::new(1, 0, 0),
+                partial_match: Metrics::new(1, 0, 0),
+            },
+        ];
+        assert_eq!(expected_entities, report.entities);
+    }
+
+    #[test]
+    fn test_partial_match_pairs_as_many_slots_as_possible() {
+        // Given
+        let gold = result(
+            Some("makeCoffee"),
+            vec![
+                slot("beverage", "beverage", 0..10),
+                slot("beverage", "beverage", 12..20),
+            ],
+        );
+        let predicted = result(
+            Some("makeCoffee"),
+            vec![
+                slot("beverage", "beverage", 5..15),
+                slot("beverage", "beverage", 8..10),
+            ],
+        );
+
+        // When
+        let report = evaluate([(&gold, &predicted)]);
+
+        // Then
+        assert_eq!(Metrics::new(2, 0, 0), report.partial_match_slots);
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        // Given
+        let gold = result(None, vec![]);
+        let predicted = result(None, vec![]);
+
+        // When
+        let report = evaluate([(&gold, &predicted)]);
+
+        // Then
+        let empty_metrics = json!({
+            "truePositives": 0,
+            "falsePositives": 0,
+            "falseNegatives": 0,
+            "precision": 0.0,
+            "recall": 0.0,
+            "f1": 0.0
+        });
+        let expected_json = json!({
+            "intents": [{
+                "intentName": null,
+                "metrics": {
+                    "truePositives": 1,
+                    "falsePositives": 0,
+                    "falseNegatives": 0,
+                    "precision": 1.0,
+                    "recall": 1.0,
+                    "f1": 1.0
+                }
+            }],
+            "overallIntent": empty_metrics,
+            "exactMatchSlots": empty_metrics,
+            "partialMatchSlots": empty_metrics,
+            "entities": []
+        });
+        assert_eq!(expected_json, serde_json::to_value(&report).unwrap());
+    }
+}
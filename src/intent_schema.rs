@@ -0,0 +1,241 @@
+//! Declaration of the slots of intents, against which parsing results can be validated
+//!
+//! `IntentParserResult::validate_against` checks that a result only refers to declared intents
+//! and slots, and that the entity and the value of each slot match the declaration. It reports
+//! `Violation`s, just like `IntentParserResult::validate` which checks the internal consistency of
+//! the result.
+
+use crate::dataset::EntityReference;
+use crate::ontology::{IntentClassifierResult, IntentParserResult, Slot, SlotValue};
+use crate::validation::Violation;
+
+/// Slots of an intent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentSchema {
+    pub name: String,
+    pub slots: Vec<SlotSchema>,
+}
+
+/// Entity of a slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlotSchema {
+    pub name: String,
+    pub entity: EntityReference,
+}
+
+impl IntentSchema {
+    pub fn slot(&self, slot_name: &str) -> Option<&SlotSchema> {
+        self.slots.iter().find(|slot| slot.name == slot_name)
+    }
+}
+
+impl EntityReference {
+    /// Returns whether the entity can be resolved to the given slot value
+    pub fn accepts_slot_value(&self, value: &SlotValue) -> bool {
+        match self {
+            EntityReference::Builtin(kind) => kind.accepts_slot_value(value),
+            EntityReference::Custom(_) => matches!(value, SlotValue::Custom(_)),
+        }
+    }
+}
+
+fn validate_slot_against(
+    path: String,
+    slot: &Slot,
+    schema: &IntentSchema,
+    violations: &mut Vec<Violation>,
+) {
+    let slot_schema = match schema.slot(&slot.slot_name) {
+        Some(slot_schema) => slot_schema,
+        None => {
+            violations.push(Violation::UnknownSlot {
+                path,
+                intent_name: schema.name.clone(),
+                slot_name: slot.slot_name.clone(),
+            });
+            return;
+        }
+    };
+    if slot.entity != slot_schema.entity.identifier() {
+        violations.push(Violation::EntityMismatch {
+            path: path.clone(),
+            entity: slot.entity.clone(),
+            expected_entity: slot_schema.entity.to_string(),
+        })
+    }
+    for value in Some(&slot.value)
+        .into_iter()
+        .chain(slot.alternatives.iter())
+    {
+        if !slot_schema.entity.accepts_slot_value(value) {
+            violations.push(Violation::SlotValueMismatch {
+                path: path.clone(),
+                entity: slot_schema.entity.to_string(),
+                value_kind: value.kind().to_string(),
+            })
+        }
+    }
+}
+
+fn validate_intent_against(
+    path: &str,
+    intent: &IntentClassifierResult,
+    slots: &[Slot],
+    schemas: &[IntentSchema],
+    violations: &mut Vec<Violation>,
+) {
+    let intent_name = match intent.intent_name {
+        Some(ref intent_name) => intent_name,
+        None => return,
+    };
+    let schema = match schemas.iter().find(|schema| &schema.name == intent_name) {
+        Some(schema) => schema,
+        None => {
+            violations.push(Violation::UnknownIntent {
+                path: format!("{}intent", path),
+                intent_name: intent_name.clone(),
+            });
+            return;
+        }
+    };
+    for (index, slot) in slots.iter().enumerate() {
+        let slot_path = format!("{}slots[{}]", path, index);
+        validate_slot_against(slot_path, slot, schema, violations)
+    }
+}
+
+impl IntentParserResult {
+    /// Checks the result against the schemas of the intents and returns all the violations found
+    ///
+    /// Slots of unknown intents are not checked, and results without intent are always valid.
+    pub fn validate_against(&self, schemas: &[IntentSchema]) -> Vec<Violation> {
+        let mut violations = vec![];
+        validate_intent_against("", &self.intent, &self.slots, schemas, &mut violations);
+        for (index, alternative) in self.alternatives.iter().enumerate() {
+            validate_intent_against(
+                &format!("alternatives[{}].", index),
+                &alternative.intent,
+                &alternative.slots,
+                schemas,
+                &mut violations,
+            );
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::builtin_entity::BuiltinEntityKind;
+    use crate::ontology::{IntentParserAlternative, NumberValue, OrdinalValue};
+    use crate::test_utils::{intent, slot};
+
+    fn schemas() -> Vec<IntentSchema> {
+        vec![IntentSchema {
+            name: "makeCoffee".to_string(),
+            slots: vec![
+                SlotSchema {
+                    name: "number_of_cups".to_string(),
+                    entity: EntityReference::Builtin(BuiltinEntityKind::Number),
+                },
+                SlotSchema {
+                    name: "beverage".to_string(),
+                    entity: EntityReference::Custom("beverage".to_string()),
+                },
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_valid_result() {
+        // Given
+        let result = IntentParserResult {
+            input: "value".to_string(),
+            intent: intent(Some("makeCoffee"), 0.8),
+            slots: vec![
+                Slot {
+                    value: SlotValue::Number(NumberValue { value: 2. }),
+                    ..slot("number_of_cups", "snips/number", 0..5)
+                },
+                slot("beverage", "beverage", 0..5),
+            ],
+            alternatives: vec![IntentParserAlternative {
+                intent: intent(None, 0.2),
+                slots: vec![],
+            }],
+        };
+
+        // When
+        let violations = result.validate_against(&schemas());
+
+        // Then
+        assert_eq!(Vec::<Violation>::new(), violations);
+    }
+
+    #[test]
+    fn test_all_violations_are_reported() {
+        // Given
+        let result = IntentParserResult {
+            input: "value".to_string(),
+            intent: intent(Some("makeCoffee"), 0.8),
+            slots: vec![
+                slot("temperature", "snips/temperature", 0..5),
+                slot("beverage", "drink", 0..5),
+                Slot {
+                    value: SlotValue::Ordinal(OrdinalValue { value: 2 }),
+                    ..slot("number_of_cups", "snips/number", 0..5)
+                },
+            ],
+            alternatives: vec![IntentParserAlternative {
+                intent: intent(Some("makeTea"), 0.2),
+                slots: vec![],
+            }],
+        };
+
+        // When
+        let violations = result.validate_against(&schemas());
+
+        // Then
+        let expected_violations = vec![
+            Violation::UnknownSlot {
+                path: "slots[0]".to_string(),
+                intent_name: "makeCoffee".to_string(),
+                slot_name: "temperature".to_string(),
+            },
+            Violation::EntityMismatch {
+                path: "slots[1]".to_string(),
+                entity: "drink".to_string(),
+                expected_entity: "beverage".to_string(),
+            },
+            Violation::SlotValueMismatch {
+                path: "slots[2]".to_string(),
+                entity: "snips/number".to_string(),
+                value_kind: "Ordinal".to_string(),
+            },
+            Violation::UnknownIntent {
+                path: "alternatives[0].intent".to_string(),
+                intent_name: "makeTea".to_string(),
+            },
+        ];
+        assert_eq!(expected_violations, violations);
+    }
+
+    #[test]
+    fn test_schema_deserialization() {
+        // Given
+        let json = r#"{
+            "name": "makeCoffee",
+            "slots": [
+                {"name": "number_of_cups", "entity": "snips/number"},
+                {"name": "beverage", "entity": "beverage"}
+            ]
+        }"#;
+
+        // When
+        let schema: IntentSchema = serde_json::from_str(json).unwrap();
+
+        // Then
+        assert_eq!(schemas()[0], schema);
+    }
+}
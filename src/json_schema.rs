@@ -0,0 +1,231 @@
+//! JSON Schema of the ontology, available with the `json_schema` feature
+//!
+//! The schema follows the draft 2020-12 of the JSON Schema specification. Every public type of
+//! the ontology is described in `$defs`, and the root of the schema refers to
+//! `IntentParserResult`. Other payloads such as builtin entities can be validated by referencing
+//! their own definition, e.g. `#/$defs/BuiltinEntity`. A copy of the schema is versioned in
+//! `schema/ontology.schema.json`.
+
+use crate::currency::Currency;
+use crate::entity::builtin_entity::{BuiltinEntity, BuiltinEntityKind};
+use crate::entity::gazetteer_entity::BuiltinGazetteerEntityKind;
+use crate::entity::grammar_entity::GrammarEntityKind;
+use crate::language::Language;
+use crate::migration::{FORMAT_VERSION, FORMAT_VERSION_KEY};
+use crate::offset::OffsetUnit;
+use crate::ontology::*;
+use crate::temperature::TemperatureUnit;
+use crate::validation::Violation;
+use schemars::generate::SchemaSettings;
+use schemars::Schema;
+use serde_json::{json, Value};
+
+const SCHEMA_ID: &str =
+    "https://github.com/snipsco/snips-nlu-ontology/blob/master/schema/ontology.schema.json";
+
+/// Returns the JSON Schema describing the JSON representation of the ontology types
+pub fn ontology_json_schema() -> Value {
+    let mut generator = SchemaSettings::draft2020_12().into_generator();
+    generator.subschema_for::<IntentParserResult>();
+    generator.subschema_for::<IntentParserAlternative>();
+    generator.subschema_for::<IntentClassifierResult>();
+    generator.subschema_for::<Slot>();
+    generator.subschema_for::<SlotValue>();
+    generator.subschema_for::<BuiltinEntity>();
+    generator.subschema_for::<BuiltinEntityKind>();
+    generator.subschema_for::<BuiltinGazetteerEntityKind>();
+    generator.subschema_for::<GrammarEntityKind>();
+    generator.subschema_for::<Language>();
+    generator.subschema_for::<Currency>();
+    generator.subschema_for::<TemperatureUnit>();
+    generator.subschema_for::<OffsetUnit>();
+    generator.subschema_for::<Violation>();
+    json!({
+        "$schema": generator.settings().meta_schema,
+        "$id": SCHEMA_ID,
+        "title": "Snips NLU ontology",
+        "$ref": "#/$defs/IntentParserResult",
+        "$defs": generator.take_definitions(true),
+    })
+}
+
+/// Declares the optional `formatVersion` marker of versioned payloads
+pub(crate) fn add_format_version_property(schema: &mut Schema) {
+    if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        properties.insert(
+            FORMAT_VERSION_KEY.to_string(),
+            json!({
+                "type": "integer",
+                "minimum": 1,
+                "maximum": FORMAT_VERSION,
+            }),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::path::Path;
+
+    const SCHEMA_PATH: &str = "schema/ontology.schema.json";
+
+    fn checked_in_schema() -> Value {
+        serde_json::from_str(include_str!("../schema/ontology.schema.json")).unwrap()
+    }
+
+    /// Validates the serialization of `value` against the definition named `definition`
+    fn assert_valid<T: Serialize>(definition: &str, value: &T) {
+        let checked_in_schema = checked_in_schema();
+        let schema = json!({
+            "$schema": checked_in_schema["$schema"],
+            "$ref": format!("#/$defs/{}", definition),
+            "$defs": checked_in_schema["$defs"],
+        });
+        let validator = jsonschema::draft202012::new(&schema).unwrap();
+        let instance = serde_json::to_value(value).unwrap();
+        let errors = validator
+            .iter_errors(&instance)
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert!(
+            errors.is_empty(),
+            "{} is not a valid {}: {:?}",
+            instance,
+            definition,
+            errors
+        );
+    }
+
+    #[test]
+    fn test_checked_in_schema_is_up_to_date() {
+        // Given
+        let schema = ontology_json_schema();
+
+        // When
+        if std::env::var("UPDATE_JSON_SCHEMA").is_ok() {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SCHEMA_PATH);
+            let content = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+            std::fs::write(path, content).unwrap();
+        }
+
+        // Then
+        assert_eq!(
+            checked_in_schema(),
+            schema,
+            "{} is outdated, run the tests with UPDATE_JSON_SCHEMA=1 to update it",
+            SCHEMA_PATH
+        );
+    }
+
+    #[test]
+    fn test_result_descriptions_match_schema() {
+        for kind in BuiltinEntityKind::all() {
+            let values: Vec<SlotValue> = serde_json::from_str(&kind.result_description()).unwrap();
+            for value in values {
+                assert_valid("SlotValue", &value);
+                assert_valid(
+                    "BuiltinEntity",
+                    &BuiltinEntity {
+                        value: "value".to_string(),
+                        range: 0..5,
+                        entity: value,
+                        alternatives: vec![],
+                        entity_kind: *kind,
+                    },
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_intent_parser_result_matches_schema() {
+        // Given
+        let slot = Slot {
+            raw_value: "tomorrow".to_string(),
+            value: SlotValue::InstantTime(InstantTimeValue {
+                value: "2017-06-14 00:00:00 +02:00".to_string(),
+                grain: Grain::Day,
+                precision: Precision::Exact,
+            }),
+            alternatives: vec![SlotValue::TimeInterval(TimeIntervalValue {
+                from: Some("2017-06-14 00:00:00 +02:00".to_string()),
+                to: None,
+            })],
+            range: 15..23,
+            entity: "snips/datetime".to_string(),
+            slot_name: "date".to_string(),
+            confidence_score: Some(0.9),
+        };
+        let result = IntentParserResult {
+            input: "book a table 4 tomorrow".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("bookRestaurant".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![
+                slot.clone(),
+                Slot::new_custom(
+                    "table".to_string(),
+                    7..12,
+                    "furniture".to_string(),
+                    "item".to_string(),
+                    None,
+                    vec![],
+                ),
+            ],
+            alternatives: vec![IntentParserAlternative {
+                intent: IntentClassifierResult {
+                    intent_name: None,
+                    confidence_score: 0.2,
+                },
+                slots: vec![slot],
+            }],
+        };
+
+        // When/Then
+        assert_valid("IntentParserResult", &result);
+        assert_valid(
+            "Violation",
+            &Violation::InvalidSlotRange {
+                path: "slots[0]".to_string(),
+                range: 15..30,
+                input_length: 23,
+            },
+        );
+    }
+
+    #[test]
+    fn test_schema_rejects_invalid_payloads() {
+        // Given
+        let schema = checked_in_schema();
+        let validator = jsonschema::draft202012::new(&schema).unwrap();
+        let missing_kind = serde_json::json!({
+            "input": "hello",
+            "intent": {"intentName": null, "confidenceScore": 0.5},
+            "slots": [{
+                "rawValue": "hello",
+                "value": {"value": "hello"},
+                "range": {"start": 0, "end": 5},
+                "entity": "greeting",
+                "slotName": "greeting"
+            }]
+        });
+        let unknown_kind = serde_json::json!({
+            "input": "hello",
+            "intent": {"intentName": null, "confidenceScore": 0.5},
+            "slots": [{
+                "rawValue": "hello",
+                "value": {"kind": "Greeting", "value": "hello"},
+                "range": {"start": 0, "end": 5},
+                "entity": "greeting",
+                "slotName": "greeting"
+            }]
+        });
+
+        // When/Then
+        assert!(!validator.is_valid(&missing_kind));
+        assert!(!validator.is_valid(&unknown_kind));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use crate::errors::*;
 use crate::language_enum;
-use failure::bail;
+use std::collections::HashMap;
 
 language_enum!([DE, EN, ES, FR, IT, PT_PT, PT_BR, JA, KO]);
 
@@ -17,6 +19,182 @@ impl Language {
             Language::KO => "Korean",
         }
     }
+
+    /// Name of the language in the language itself
+    pub fn native_name(&self) -> &'static str {
+        match *self {
+            Language::DE => "Deutsch",
+            Language::EN => "English",
+            Language::ES => "Español",
+            Language::FR => "Français",
+            Language::IT => "Italiano",
+            Language::PT_PT => "Português europeu",
+            Language::PT_BR => "Português brasileiro",
+            Language::JA => "日本語",
+            Language::KO => "한국어",
+        }
+    }
+
+    /// Two-letter ISO 639-1 code of the language, regional variants sharing the same code
+    pub fn iso_639_1(&self) -> &'static str {
+        match *self {
+            Language::DE => "de",
+            Language::EN => "en",
+            Language::ES => "es",
+            Language::FR => "fr",
+            Language::IT => "it",
+            Language::PT_PT | Language::PT_BR => "pt",
+            Language::JA => "ja",
+            Language::KO => "ko",
+        }
+    }
+
+    /// Three-letter ISO 639-3 code of the language, regional variants sharing the same code
+    pub fn iso_639_3(&self) -> &'static str {
+        match *self {
+            Language::DE => "deu",
+            Language::EN => "eng",
+            Language::ES => "spa",
+            Language::FR => "fra",
+            Language::IT => "ita",
+            Language::PT_PT | Language::PT_BR => "por",
+            Language::JA => "jpn",
+            Language::KO => "kor",
+        }
+    }
+
+    /// ISO 15924 code of the script in which the language is written
+    pub fn script(&self) -> &'static str {
+        match *self {
+            Language::JA => "Jpan",
+            Language::KO => "Kore",
+            _ => "Latn",
+        }
+    }
+
+    /// BCP-47 tag of the language, which includes the region of regional variants only
+    pub fn bcp47_tag(&self) -> &'static str {
+        match *self {
+            Language::PT_PT => "pt-PT",
+            Language::PT_BR => "pt-BR",
+            _ => self.iso_639_1(),
+        }
+    }
+
+    /// Parses a BCP-47 tag such as "en-US" or "zh-Hant-TW", or a POSIX locale such as
+    /// "pt_BR.UTF-8"
+    ///
+    /// The language subtag can be either an ISO 639-1 or an ISO 639-3 code, and the region is
+    /// only used to pick a regional variant. Following CLDR, a Portuguese locale resolves to
+    /// `PT_BR` when its region is Brazil or missing, and to `PT_PT` otherwise.
+    pub fn from_locale(locale: &str) -> Result<Language> {
+        let unknown_language = || OntologyError::UnknownLanguage(locale.to_string());
+        // POSIX locales may end with an encoding and a modifier, e.g. "de_DE.UTF-8@euro"
+        let tag = locale.split(['.', '@']).next().unwrap_or("");
+        let mut subtags = tag.split(['-', '_']);
+        let language = subtags.next().unwrap_or("").to_lowercase();
+        // The region comes after the optional extended language and script subtags, and before
+        // the variants and the extensions introduced by a singleton
+        let region = subtags
+            .take_while(|subtag| subtag.len() > 1)
+            .find(|subtag| {
+                (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(|region| region.to_uppercase());
+        Ok(match &*language {
+            "de" | "deu" => Language::DE,
+            "en" | "eng" => Language::EN,
+            "es" | "spa" => Language::ES,
+            "fr" | "fra" => Language::FR,
+            "it" | "ita" => Language::IT,
+            "pt" | "por" => match region.as_deref() {
+                None | Some("BR") => Language::PT_BR,
+                Some(_) => Language::PT_PT,
+            },
+            "ja" | "jpn" => Language::JA,
+            "ko" | "kor" => Language::KO,
+            _ => return Err(unknown_language()),
+        })
+    }
+
+    /// Variant which stands for the language as a whole, European Portuguese for Portuguese
+    ///
+    /// Languages without regional variants are their own base language.
+    pub fn base_language(&self) -> Language {
+        match *self {
+            Language::PT_BR => Language::PT_PT,
+            language => language,
+        }
+    }
+
+    /// Returns whether both languages are variants of the same language
+    pub fn is_variant_of(&self, other: Language) -> bool {
+        self.base_language() == other.base_language()
+    }
+
+    /// Languages to try in order when resources are missing for this language, starting with
+    /// the language itself and followed by its base language and its other regional variants
+    pub fn fallback_chain(&self) -> Vec<Language> {
+        LanguageFallbacks::default().chain(*self)
+    }
+
+    pub fn supported_builtin_entities(&self) -> Vec<BuiltinEntityKind> {
+        BuiltinEntityKind::all()
+            .iter()
+            .filter(|kind| kind.is_supported(*self))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Configurable fallback chains between languages
+///
+/// By default, a language falls back to its base language and then to its other regional
+/// variants, in the order of `Language::all()`. Custom fallbacks replace this default for a given
+/// language.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LanguageFallbacks {
+    custom_fallbacks: HashMap<Language, Vec<Language>>,
+}
+
+impl LanguageFallbacks {
+    /// Replaces the fallbacks of `language` with `fallbacks`, an empty list disabling fallbacks
+    pub fn with_fallbacks(mut self, language: Language, fallbacks: Vec<Language>) -> Self {
+        self.custom_fallbacks.insert(language, fallbacks);
+        self
+    }
+
+    /// Languages to try in order for `language`, starting with `language` itself
+    pub fn chain(&self, language: Language) -> Vec<Language> {
+        let fallbacks = match self.custom_fallbacks.get(&language) {
+            Some(fallbacks) => fallbacks.clone(),
+            None => Some(language.base_language())
+                .into_iter()
+                .chain(
+                    Language::all()
+                        .iter()
+                        .cloned()
+                        .filter(|other| other.is_variant_of(language)),
+                )
+                .collect(),
+        };
+        let mut chain = vec![language];
+        for fallback in fallbacks {
+            if !chain.contains(&fallback) {
+                chain.push(fallback);
+            }
+        }
+        chain
+    }
+
+    /// Returns the first value found by `lookup` along the fallback chain of `language`
+    pub fn resolve<T, F>(&self, language: Language, lookup: F) -> Option<T>
+    where
+        F: FnMut(Language) -> Option<T>,
+    {
+        self.chain(language).into_iter().find_map(lookup)
+    }
 }
 
 #[cfg(test)]
@@ -35,4 +213,105 @@ mod test {
         let lang = Language::from_str("EN");
         assert!(lang.is_ok());
     }
+
+    #[test]
+    fn init_from_unknown_string_fails() {
+        let lang = Language::from_str("klingon");
+        assert_eq!(
+            Err(OntologyError::UnknownLanguage("klingon".to_string())),
+            lang
+        );
+    }
+
+    #[test]
+    fn init_from_locale_works() {
+        let locales = vec![
+            ("en-US", Language::EN),
+            ("EN", Language::EN),
+            ("pt-BR", Language::PT_BR),
+            ("pt_PT.UTF-8", Language::PT_PT),
+            ("pt-AO", Language::PT_PT),
+            ("pt", Language::PT_BR),
+            ("ja-JP", Language::JA),
+            ("ja-Jpan-JP-u-ca-japanese", Language::JA),
+            ("de_DE.UTF-8@euro", Language::DE),
+            ("es-419", Language::ES),
+            ("fra", Language::FR),
+            ("ko-KR", Language::KO),
+        ];
+        for (locale, language) in locales {
+            assert_eq!(Ok(language), Language::from_locale(locale), "{}", locale);
+        }
+        for locale in &["", "C", "zh-Hant-TW", "english"] {
+            assert_eq!(
+                Err(OntologyError::UnknownLanguage(locale.to_string())),
+                Language::from_locale(locale)
+            );
+        }
+    }
+
+    #[test]
+    fn bcp47_tag_round_trips() {
+        for language in Language::all() {
+            assert_eq!(Ok(*language), Language::from_locale(language.bcp47_tag()));
+        }
+    }
+
+    #[test]
+    fn default_fallback_chains_work() {
+        assert_eq!(Language::PT_PT, Language::PT_BR.base_language());
+        assert_eq!(Language::FR, Language::FR.base_language());
+        assert_eq!(
+            vec![Language::PT_BR, Language::PT_PT],
+            Language::PT_BR.fallback_chain()
+        );
+        assert_eq!(
+            vec![Language::PT_PT, Language::PT_BR],
+            Language::PT_PT.fallback_chain()
+        );
+        assert_eq!(vec![Language::JA], Language::JA.fallback_chain());
+    }
+
+    #[test]
+    fn custom_fallback_chains_work() {
+        let fallbacks = LanguageFallbacks::default()
+            .with_fallbacks(Language::PT_BR, vec![])
+            .with_fallbacks(
+                Language::ES,
+                vec![Language::PT_PT, Language::ES, Language::IT],
+            );
+        assert_eq!(vec![Language::PT_BR], fallbacks.chain(Language::PT_BR));
+        assert_eq!(
+            vec![Language::PT_PT, Language::PT_BR],
+            fallbacks.chain(Language::PT_PT)
+        );
+        assert_eq!(
+            vec![Language::ES, Language::PT_PT, Language::IT],
+            fallbacks.chain(Language::ES)
+        );
+    }
+
+    #[test]
+    fn resolve_follows_fallback_chain() {
+        let mut resources = HashMap::new();
+        resources.insert(Language::PT_PT, "recursos");
+        let fallbacks = LanguageFallbacks::default();
+        assert_eq!(
+            Some("recursos"),
+            fallbacks.resolve(Language::PT_BR, |language| resources
+                .get(&language)
+                .cloned())
+        );
+        assert_eq!(
+            None,
+            fallbacks.resolve(Language::EN, |language| resources.get(&language).cloned())
+        );
+    }
+
+    #[test]
+    fn korean_does_not_support_music_entities() {
+        let entities = Language::KO.supported_builtin_entities();
+        assert!(entities.contains(&BuiltinEntityKind::Number));
+        assert!(!entities.contains(&BuiltinEntityKind::MusicArtist));
+    }
 }
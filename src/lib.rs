@@ -1,13 +1,56 @@
 #[macro_use]
 extern crate serde_derive;
 
+pub mod alternatives;
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "messagepack"))]
+pub mod binary;
+pub mod confidence_policy;
+pub mod confusion_matrix;
+pub mod currency;
+pub mod dataset;
+pub mod diff;
+mod duration;
 pub mod entity;
 pub mod errors;
+pub mod evaluation;
+pub mod intent_schema;
+#[cfg(feature = "json_schema")]
+pub mod json_schema;
 pub mod language;
 pub mod macros;
+pub mod migration;
+pub mod offset;
 mod ontology;
+pub mod overlap;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+pub mod temperature;
+#[cfg(test)]
+mod test_utils;
+#[cfg(feature = "chrono")]
+mod time_interval;
+pub mod validation;
+#[cfg(any(feature = "bincode", feature = "cbor", feature = "messagepack"))]
+pub use binary::*;
+pub use confidence_policy::*;
+pub use confusion_matrix::*;
+pub use currency::*;
+pub use dataset::*;
+pub use diff::*;
+pub use duration::*;
 pub use entity::builtin_entity::{BuiltinEntity, BuiltinEntityKind, IntoBuiltinEntityKind};
+pub use entity::custom_entity::*;
 pub use entity::gazetteer_entity::*;
 pub use entity::grammar_entity::*;
+pub use errors::OntologyError;
+pub use evaluation::*;
+pub use intent_schema::*;
+#[cfg(feature = "json_schema")]
+pub use json_schema::*;
 pub use language::*;
+pub use migration::*;
+pub use offset::*;
 pub use ontology::*;
+pub use overlap::*;
+pub use temperature::*;
+pub use validation::*;
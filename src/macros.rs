@@ -2,6 +2,7 @@
 macro_rules! language_enum {
     ([$($language:ident),*]) => {
         #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+        #[cfg_attr(feature = "json_schema", derive(::schemars::JsonSchema))]
         #[allow(non_camel_case_types)]
         pub enum Language {
             $( $language, )*
@@ -15,13 +16,13 @@ macro_rules! language_enum {
         }
 
         impl ::std::str::FromStr for Language {
-            type Err=::failure::Error;
+            type Err=$crate::errors::OntologyError;
             fn from_str(s: &str) -> ::std::result::Result<Language, Self::Err> {
                 match &*s.to_uppercase() {
                     $(
                         stringify!($language) => Ok(Language::$language),
                     )*
-                    _ => bail!("Unknown language: {}", s)
+                    _ => Err($crate::errors::OntologyError::UnknownLanguage(s.to_string()))
                 }
             }
         }
@@ -42,6 +43,7 @@ macro_rules! language_enum {
 macro_rules! enum_kind {
     ($kindname:ident, [$($varname:ident),*]) => {
         #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+        #[cfg_attr(feature = "json_schema", derive(::schemars::JsonSchema))]
         pub enum $kindname {
             $( $varname ),*
         }
@@ -54,13 +56,16 @@ macro_rules! enum_kind {
         }
 
         impl ::std::str::FromStr for $kindname {
-            type Err=String;
+            type Err=$crate::errors::OntologyError;
             fn from_str(s: &str) -> ::std::result::Result<$kindname, Self::Err> {
                 match s {
                     $(
                         stringify!($varname) => Ok($kindname::$varname),
                     )*
-                    _ => Err(format!("{} is not a known {}", s, stringify!($kindname)))
+                    _ => Err($crate::errors::OntologyError::UnknownEntityKind {
+                        kind: stringify!($kindname),
+                        value: s.to_string(),
+                    })
                 }
             }
         }
@@ -87,6 +92,7 @@ macro_rules! enum_kind {
 macro_rules! sub_entity_kind {
     ($kindname:ident, [$($varname:ident),*]) => {
         #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+        #[cfg_attr(feature = "json_schema", derive(::schemars::JsonSchema))]
         pub enum $kindname {
             $( $varname ),*
         }
@@ -99,13 +105,16 @@ macro_rules! sub_entity_kind {
         }
 
         impl ::std::str::FromStr for $kindname {
-            type Err=String;
+            type Err=$crate::errors::OntologyError;
             fn from_str(s: &str) -> ::std::result::Result<$kindname, Self::Err> {
                 match s {
                     $(
                         stringify!($varname) => Ok($kindname::$varname),
                     )*
-                    _ => Err(format!("{} is not a known {}", s, stringify!($kindname)))
+                    _ => Err($crate::errors::OntologyError::UnknownEntityKind {
+                        kind: stringify!($kindname),
+                        value: s.to_string(),
+                    })
                 }
             }
         }
@@ -131,12 +140,15 @@ macro_rules! sub_entity_kind {
         }
 
         impl $kindname {
-            pub fn from_identifier(identifier: &str) -> Result<Self> {
+            pub fn from_identifier(identifier: &str) -> $crate::errors::Result<Self> {
                 $kindname::all()
                     .iter()
                     .find(|kind| kind.identifier() == identifier)
                     .map(|k| k.clone())
-                    .ok_or(format_err!("Unknown {} identifier: {}", stringify!($kindname), identifier))
+                    .ok_or_else(|| $crate::errors::OntologyError::UnknownEntityKind {
+                        kind: stringify!($kindname),
+                        value: identifier.to_string(),
+                    })
             }
         }
     }
@@ -0,0 +1,437 @@
+//! Versioning of the JSON format and migration of payloads written by older releases
+//!
+//! `IntentParserResult` and `BuiltinEntity` are serialized with a `formatVersion` marker, which
+//! is incremented each time a release changes their JSON representation in a way that prevents
+//! older payloads from being deserialized:
+//!
+//! | Version | Releases        | Changes                                                        |
+//! |---------|-----------------|----------------------------------------------------------------|
+//! | 1       | 0.54.1 - 0.62.0 | `intent`, `slots` and slot `range` may be `null`               |
+//! | 2       | 0.63.0 - 0.63.2 | `intent`, `slots` and `range` are always set                   |
+//! | 3       | 0.64.0 - 0.66.0 | `probability` of the intent is renamed to `confidenceScore`    |
+//! | 4       | 0.67.0 -        | `alternatives` are added to results, slots and builtin entities |
+//!
+//! Releases which only added new values, such as the datetime subtypes and geographic entities
+//! of 0.65.0, did not change the format. Payloads written before the marker was introduced are
+//! identified by their shape, formats which cannot be told apart being upgraded the same way.
+
+use crate::entity::builtin_entity::BuiltinEntity;
+use crate::errors::*;
+use crate::ontology::IntentParserResult;
+use serde_json::{json, Map, Value};
+
+/// Current version of the JSON format
+pub const FORMAT_VERSION: u32 = 4;
+
+/// Name of the JSON field holding the format version
+pub const FORMAT_VERSION_KEY: &str = "formatVersion";
+
+/// First release of each format version
+const FORMAT_VERSION_RELEASES: &[(u32, (u32, u32, u32))] = &[
+    (1, (0, 54, 1)),
+    (2, (0, 63, 0)),
+    (3, (0, 64, 0)),
+    (4, (0, 67, 0)),
+];
+
+fn parse_release(release: &str) -> Result<(u32, u32, u32)> {
+    let parts = release
+        .split('.')
+        .map(|part| part.parse::<u32>())
+        .collect::<::std::result::Result<Vec<_>, _>>()
+        .map_err(|_| OntologyError::conversion_failure(format!("Invalid release: {}", release)))?;
+    match parts.as_slice() {
+        [major, minor, patch] => Ok((*major, *minor, *patch)),
+        _ => Err(OntologyError::conversion_failure(format!(
+            "Invalid release: {}",
+            release
+        ))),
+    }
+}
+
+/// Returns the version of the JSON format written by a release of the crate, e.g. "0.64.2"
+pub fn format_version_of_release(release: &str) -> Result<u32> {
+    let release_number = parse_release(release)?;
+    FORMAT_VERSION_RELEASES
+        .iter()
+        .rev()
+        .find(|(_, first_release)| *first_release <= release_number)
+        .map(|(version, _)| *version)
+        .ok_or_else(|| {
+            OntologyError::conversion_failure(format!(
+                "Release {} predates versioned formats",
+                release
+            ))
+        })
+}
+
+fn marked_format_version(value: &Value) -> Result<Option<u32>> {
+    match value.get(FORMAT_VERSION_KEY) {
+        None => Ok(None),
+        Some(version) => {
+            let version = version.as_u64().ok_or_else(|| {
+                OntologyError::conversion_failure(format!("Invalid format version: {}", version))
+            })?;
+            if version < 1 || version > u64::from(FORMAT_VERSION) {
+                return Err(OntologyError::conversion_failure(format!(
+                    "Unsupported format version: {}",
+                    version
+                )));
+            }
+            Ok(Some(version as u32))
+        }
+    }
+}
+
+fn as_object_mut(value: &mut Value) -> Result<&mut Map<String, Value>> {
+    if !value.is_object() {
+        return Err(OntologyError::conversion_failure(format!(
+            "Expected a JSON object but found: {}",
+            value
+        )));
+    }
+    Ok(value.as_object_mut().unwrap())
+}
+
+/// Returns the format version of an intent parser result payload
+pub fn intent_parser_result_format_version(value: &Value) -> Result<u32> {
+    if let Some(version) = marked_format_version(value)? {
+        return Ok(version);
+    }
+    if value.get("alternatives").is_some() {
+        return Ok(4);
+    }
+    let intent = value.get("intent").ok_or_else(|| {
+        OntologyError::conversion_failure(format!(
+            "Missing intent in intent parser result: {}",
+            value
+        ))
+    })?;
+    if intent.get("confidenceScore").is_some() {
+        return Ok(3);
+    }
+    let has_null_range = value
+        .get("slots")
+        .and_then(|slots| slots.as_array())
+        .map(|slots| slots.iter().any(|slot| slot["range"].is_null()))
+        .unwrap_or(false);
+    if intent.is_null() || value["slots"].is_null() || has_null_range {
+        return Ok(1);
+    }
+    Ok(2)
+}
+
+/// Returns the format version of a builtin entity payload
+///
+/// Builtin entities did not change before version 4, hence older payloads are reported as
+/// version 3.
+pub fn builtin_entity_format_version(value: &Value) -> Result<u32> {
+    if let Some(version) = marked_format_version(value)? {
+        return Ok(version);
+    }
+    if value.get("alternatives").is_some() {
+        Ok(4)
+    } else {
+        Ok(3)
+    }
+}
+
+/// Converts a range expressed in bytes, as found with `str::find`, to Unicode scalar values
+fn char_range(input: &str, byte_start: usize, byte_end: usize) -> Value {
+    let start = input[..byte_start].chars().count();
+    let end = start + input[byte_start..byte_end].chars().count();
+    json!({ "start": start, "end": end })
+}
+
+/// Fills the missing intent, slots and slot ranges
+///
+/// A missing intent means that no intent was found, and a missing range is looked up in the
+/// input using the raw value of the slot.
+fn migrate_intent_parser_result_v1(value: &mut Value) -> Result<()> {
+    let input = value["input"]
+        .as_str()
+        .ok_or_else(|| {
+            OntologyError::conversion_failure(format!(
+                "Missing input in intent parser result: {}",
+                value
+            ))
+        })?
+        .to_string();
+    let result = as_object_mut(value)?;
+    if result.get("intent").map(Value::is_null).unwrap_or(true) {
+        result.insert(
+            "intent".to_string(),
+            json!({ "intentName": null, "probability": 0.0 }),
+        );
+    }
+    if result.get("slots").map(Value::is_null).unwrap_or(true) {
+        result.insert("slots".to_string(), json!([]));
+    }
+    for slot in result["slots"].as_array_mut().into_iter().flatten() {
+        if !slot["range"].is_null() {
+            continue;
+        }
+        let raw_value = slot["rawValue"].as_str().ok_or_else(|| {
+            OntologyError::conversion_failure(format!("Missing raw value in slot: {}", slot))
+        })?;
+        let byte_start = input.find(raw_value).ok_or_else(|| {
+            OntologyError::conversion_failure(format!(
+                "Cannot find the range of slot value '{}' in '{}'",
+                raw_value, input
+            ))
+        })?;
+        let range = char_range(&input, byte_start, byte_start + raw_value.len());
+        as_object_mut(slot)?.insert("range".to_string(), range);
+    }
+    Ok(())
+}
+
+/// Renames the `probability` of the intent to `confidenceScore`
+fn migrate_intent_parser_result_v2(value: &mut Value) -> Result<()> {
+    let intent = as_object_mut(&mut value["intent"])?;
+    if let Some(probability) = intent.remove("probability") {
+        intent.insert("confidenceScore".to_string(), probability);
+    }
+    Ok(())
+}
+
+/// Adds the empty alternatives of the result and of its slots
+fn migrate_intent_parser_result_v3(value: &mut Value) -> Result<()> {
+    for slot in value["slots"].as_array_mut().into_iter().flatten() {
+        as_object_mut(slot)?
+            .entry("alternatives")
+            .or_insert_with(|| json!([]));
+    }
+    as_object_mut(value)?
+        .entry("alternatives")
+        .or_insert_with(|| json!([]));
+    Ok(())
+}
+
+/// Upgrades an intent parser result written by any past release to the current format
+pub fn migrate_intent_parser_result(mut value: Value) -> Result<IntentParserResult> {
+    let version = intent_parser_result_format_version(&value)?;
+    if version <= 1 {
+        migrate_intent_parser_result_v1(&mut value)?;
+    }
+    if version <= 2 {
+        migrate_intent_parser_result_v2(&mut value)?;
+    }
+    if version <= 3 {
+        migrate_intent_parser_result_v3(&mut value)?;
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Upgrades a builtin entity written by any past release to the current format
+pub fn migrate_builtin_entity(mut value: Value) -> Result<BuiltinEntity> {
+    if builtin_entity_format_version(&value)? <= 3 {
+        as_object_mut(&mut value)?
+            .entry("alternatives")
+            .or_insert_with(|| json!([]));
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::builtin_entity::BuiltinEntityKind;
+    use crate::ontology::*;
+
+    /// Expected migration of the `intent_parser_result_v<version>.json` fixtures
+    fn expected_intent_parser_result(version: u32) -> IntentParserResult {
+        let input = "réserve une table pour 3 demain".to_string();
+        let number_slot = Slot {
+            raw_value: "3".to_string(),
+            value: SlotValue::Number(NumberValue { value: 3. }),
+            alternatives: vec![],
+            range: 23..24,
+            entity: "snips/number".to_string(),
+            slot_name: "count".to_string(),
+            confidence_score: if version >= 2 { Some(0.9) } else { None },
+        };
+        let date_slot = Slot {
+            raw_value: "demain".to_string(),
+            value: SlotValue::InstantTime(InstantTimeValue {
+                value: "2019-09-10 00:00:00 +02:00".to_string(),
+                grain: Grain::Day,
+                precision: Precision::Exact,
+            }),
+            alternatives: if version >= 4 {
+                vec![SlotValue::TimeInterval(TimeIntervalValue {
+                    from: Some("2019-09-10 00:00:00 +02:00".to_string()),
+                    to: Some("2019-09-11 00:00:00 +02:00".to_string()),
+                })]
+            } else {
+                vec![]
+            },
+            range: 25..31,
+            entity: "snips/datetime".to_string(),
+            slot_name: "date".to_string(),
+            confidence_score: if version >= 2 { Some(0.8) } else { None },
+        };
+        IntentParserResult {
+            input,
+            intent: IntentClassifierResult {
+                intent_name: Some("bookRestaurant".to_string()),
+                confidence_score: 0.75,
+            },
+            slots: vec![number_slot, date_slot],
+            alternatives: if version >= 4 {
+                vec![IntentParserAlternative {
+                    intent: IntentClassifierResult {
+                        intent_name: None,
+                        confidence_score: 0.2,
+                    },
+                    slots: vec![],
+                }]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    fn intent_parser_result_fixture(version: u32) -> &'static str {
+        match version {
+            1 => include_str!("../fixtures/migration/intent_parser_result_v1.json"),
+            2 => include_str!("../fixtures/migration/intent_parser_result_v2.json"),
+            3 => include_str!("../fixtures/migration/intent_parser_result_v3.json"),
+            4 => include_str!("../fixtures/migration/intent_parser_result_v4.json"),
+            _ => panic!("No fixture for format version {}", version),
+        }
+    }
+
+    fn builtin_entity_fixture(version: u32) -> &'static str {
+        match version {
+            // Builtin entities did not change before version 4
+            1..=3 => include_str!("../fixtures/migration/builtin_entity_v3.json"),
+            4 => include_str!("../fixtures/migration/builtin_entity_v4.json"),
+            _ => panic!("No fixture for format version {}", version),
+        }
+    }
+
+    /// Releases listed in the changelog, from the most recent to the oldest one
+    fn changelog_releases() -> Vec<&'static str> {
+        include_str!("../CHANGELOG.md")
+            .lines()
+            .filter_map(|line| line.strip_prefix("## ["))
+            .filter_map(|line| line.split(']').next())
+            .filter(|release| *release != "Unreleased")
+            .collect()
+    }
+
+    #[test]
+    fn test_format_version_of_release() {
+        assert_eq!(1, format_version_of_release("0.54.1").unwrap());
+        assert_eq!(1, format_version_of_release("0.62.0").unwrap());
+        assert_eq!(2, format_version_of_release("0.63.2").unwrap());
+        assert_eq!(3, format_version_of_release("0.65.0").unwrap());
+        assert_eq!(4, format_version_of_release("0.67.2").unwrap());
+        assert!(format_version_of_release("0.53.0").is_err());
+        assert!(format_version_of_release("latest").is_err());
+    }
+
+    #[test]
+    fn test_migrate_intent_parser_result_of_every_release() {
+        let releases = changelog_releases();
+        assert!(releases.len() > 30);
+        for release in releases {
+            // Given
+            let version = format_version_of_release(release).unwrap();
+            let payload: Value =
+                serde_json::from_str(intent_parser_result_fixture(version)).unwrap();
+
+            // When
+            let detected_version = intent_parser_result_format_version(&payload).unwrap();
+            let migrated = migrate_intent_parser_result(payload).unwrap();
+
+            // Then
+            assert_eq!(version, detected_version, "release {}", release);
+            assert_eq!(
+                expected_intent_parser_result(version),
+                migrated,
+                "release {}",
+                release
+            );
+        }
+    }
+
+    #[test]
+    fn test_migrate_builtin_entity_of_every_release() {
+        for release in changelog_releases() {
+            // Given
+            let version = format_version_of_release(release).unwrap();
+            let payload: Value = serde_json::from_str(builtin_entity_fixture(version)).unwrap();
+
+            // When
+            let migrated = migrate_builtin_entity(payload).unwrap();
+
+            // Then
+            let expected_alternatives = if version >= 4 {
+                vec![SlotValue::Number(NumberValue { value: 30. })]
+            } else {
+                vec![]
+            };
+            let expected_entity = BuiltinEntity {
+                value: "trois".to_string(),
+                range: 10..15,
+                entity: SlotValue::Number(NumberValue { value: 3. }),
+                alternatives: expected_alternatives,
+                entity_kind: BuiltinEntityKind::Number,
+            };
+            assert_eq!(expected_entity, migrated, "release {}", release);
+        }
+    }
+
+    #[test]
+    fn test_migrate_result_without_intent_nor_slots() {
+        // Given
+        let payload = json!({
+            "input": "hello world",
+            "intent": null,
+            "slots": null
+        });
+
+        // When
+        let migrated = migrate_intent_parser_result(payload).unwrap();
+
+        // Then
+        let expected_result = IntentParserResult {
+            input: "hello world".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: None,
+                confidence_score: 0.0,
+            },
+            slots: vec![],
+            alternatives: vec![],
+        };
+        assert_eq!(expected_result, migrated);
+    }
+
+    #[test]
+    fn test_current_format_round_trip() {
+        // Given
+        let result = expected_intent_parser_result(FORMAT_VERSION);
+
+        // When
+        let serialized = serde_json::to_value(&result).unwrap();
+
+        // Then
+        assert_eq!(json!(FORMAT_VERSION), serialized[FORMAT_VERSION_KEY]);
+        assert_eq!(result, migrate_intent_parser_result(serialized).unwrap());
+    }
+
+    #[test]
+    fn test_unsupported_format_version() {
+        // Given
+        let mut payload = serde_json::to_value(expected_intent_parser_result(4)).unwrap();
+        payload[FORMAT_VERSION_KEY] = json!(FORMAT_VERSION + 1);
+
+        // When
+        let migrated = migrate_intent_parser_result(payload);
+
+        // Then
+        assert!(migrated.is_err());
+    }
+}
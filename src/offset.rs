@@ -0,0 +1,249 @@
+//! Units used to express offsets in a text
+//!
+//! The `range` of a `Slot` or of a `BuiltinEntity` is expressed in Unicode scalar values, i.e.
+//! in Rust `char`s, and refers to the input text which has been parsed. This is the unit used by
+//! the Python library, but neither the byte offsets used to slice Rust strings nor the UTF-16
+//! code units used by Java and Kotlin strings. The helpers below convert offsets between these
+//! units.
+
+use crate::entity::builtin_entity::BuiltinEntity;
+use crate::errors::*;
+use crate::ontology::{IntentParserAlternative, IntentParserResult, Slot};
+use std::ops::Range;
+
+/// Unit in which the `range` of slots and builtin entities is expressed
+pub const RANGE_OFFSET_UNIT: OffsetUnit = OffsetUnit::Char;
+
+/// Unit of an offset in a text
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum OffsetUnit {
+    /// Offset in bytes of the UTF-8 encoding of the text
+    Byte,
+    /// Offset in Unicode scalar values
+    Char,
+    /// Offset in code units of the UTF-16 encoding of the text
+    Utf16,
+}
+
+impl OffsetUnit {
+    pub fn all() -> &'static [OffsetUnit] {
+        static ALL: &[OffsetUnit] = &[OffsetUnit::Byte, OffsetUnit::Char, OffsetUnit::Utf16];
+        ALL
+    }
+
+    fn len(self, c: char) -> usize {
+        match self {
+            OffsetUnit::Byte => c.len_utf8(),
+            OffsetUnit::Char => 1,
+            OffsetUnit::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+/// Converts an offset in `text` from one unit to another
+///
+/// An error is returned when the offset is past the end of the text, or when it does not fall
+/// on a character boundary, e.g. in the middle of a multi-byte UTF-8 sequence.
+pub fn convert_offset(
+    text: &str,
+    offset: usize,
+    from: OffsetUnit,
+    to: OffsetUnit,
+) -> Result<usize> {
+    let mut from_offset = 0;
+    let mut to_offset = 0;
+    for c in text.chars() {
+        if from_offset >= offset {
+            break;
+        }
+        from_offset += from.len(c);
+        to_offset += to.len(c);
+    }
+    if from_offset < offset {
+        return Err(OntologyError::conversion_failure(format!(
+            "Offset {} is out of bounds of text '{}'",
+            offset, text
+        )));
+    }
+    if from_offset > offset {
+        return Err(OntologyError::conversion_failure(format!(
+            "Offset {} does not fall on a character boundary of text '{}'",
+            offset, text
+        )));
+    }
+    Ok(to_offset)
+}
+
+/// Converts a range in `text` from one unit to another
+pub fn convert_range(
+    text: &str,
+    range: &Range<usize>,
+    from: OffsetUnit,
+    to: OffsetUnit,
+) -> Result<Range<usize>> {
+    Ok(convert_offset(text, range.start, from, to)?..convert_offset(text, range.end, from, to)?)
+}
+
+impl Slot {
+    /// Returns the range of the slot in `input` expressed in `unit`
+    pub fn range_in(&self, input: &str, unit: OffsetUnit) -> Result<Range<usize>> {
+        convert_range(input, &self.range, RANGE_OFFSET_UNIT, unit)
+    }
+}
+
+impl BuiltinEntity {
+    /// Returns the range of the entity in `input` expressed in `unit`
+    pub fn range_in(&self, input: &str, unit: OffsetUnit) -> Result<Range<usize>> {
+        convert_range(input, &self.range, RANGE_OFFSET_UNIT, unit)
+    }
+}
+
+impl IntentParserResult {
+    /// Converts a range in the input from one unit to another
+    pub fn convert_range(
+        &self,
+        range: &Range<usize>,
+        from: OffsetUnit,
+        to: OffsetUnit,
+    ) -> Result<Range<usize>> {
+        convert_range(&self.input, range, from, to)
+    }
+
+    /// Returns the range of `slot` in the input expressed in `unit`
+    pub fn slot_range(&self, slot: &Slot, unit: OffsetUnit) -> Result<Range<usize>> {
+        slot.range_in(&self.input, unit)
+    }
+
+    /// Returns a copy of the result in which the ranges of the slots, including the slots of the
+    /// alternatives, are converted from one unit to another
+    ///
+    /// This is meant for callers which index the input in a unit other than
+    /// `RANGE_OFFSET_UNIT`, the ranges of the returned result no longer follow the convention of
+    /// the ontology.
+    pub fn with_converted_ranges(self, from: OffsetUnit, to: OffsetUnit) -> Result<Self> {
+        if from == to {
+            return Ok(self);
+        }
+        let input = self.input;
+        let convert_slots = |slots: Vec<Slot>| -> Result<Vec<Slot>> {
+            slots
+                .into_iter()
+                .map(|slot| {
+                    Ok(Slot {
+                        range: convert_range(&input, &slot.range, from, to)?,
+                        ..slot
+                    })
+                })
+                .collect()
+        };
+        let slots = convert_slots(self.slots)?;
+        let alternatives = self
+            .alternatives
+            .into_iter()
+            .map(|alternative| {
+                Ok(IntentParserAlternative {
+                    slots: convert_slots(alternative.slots)?,
+                    ..alternative
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IntentParserResult {
+            input,
+            intent: self.intent,
+            slots,
+            alternatives,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::{IntentClassifierResult, SlotValue};
+
+    #[test]
+    fn test_convert_offset() {
+        // Given
+        // "é" is 2 bytes long in UTF-8, "𝄞" is 4 bytes long in UTF-8 and 2 units long in UTF-16
+        let text = "é𝄞 東京";
+
+        // When/Then
+        assert_eq!(
+            3,
+            convert_offset(text, 2, OffsetUnit::Char, OffsetUnit::Utf16).unwrap()
+        );
+        assert_eq!(
+            6,
+            convert_offset(text, 2, OffsetUnit::Char, OffsetUnit::Byte).unwrap()
+        );
+        assert_eq!(
+            4,
+            convert_offset(text, 10, OffsetUnit::Byte, OffsetUnit::Char).unwrap()
+        );
+        assert_eq!(
+            13,
+            convert_offset(text, 6, OffsetUnit::Utf16, OffsetUnit::Byte).unwrap()
+        );
+        assert_eq!(
+            0,
+            convert_offset(text, 0, OffsetUnit::Utf16, OffsetUnit::Byte).unwrap()
+        );
+        assert!(convert_offset(text, 1, OffsetUnit::Byte, OffsetUnit::Char).is_err());
+        assert!(convert_offset(text, 2, OffsetUnit::Utf16, OffsetUnit::Char).is_err());
+        assert!(convert_offset(text, 6, OffsetUnit::Char, OffsetUnit::Byte).is_err());
+        assert!(convert_offset(text, 6, OffsetUnit::Char, OffsetUnit::Char).is_err());
+    }
+
+    #[test]
+    fn test_with_converted_ranges() {
+        // Given
+        let input = "明日の東京の天気".to_string();
+        let slot = Slot::new_custom(
+            "東京".to_string(),
+            3..5,
+            "location".to_string(),
+            "city".to_string(),
+            None,
+            vec![],
+        );
+        let result = IntentParserResult {
+            input: input.clone(),
+            intent: IntentClassifierResult {
+                intent_name: Some("getWeather".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![slot.clone()],
+            alternatives: vec![IntentParserAlternative {
+                intent: IntentClassifierResult {
+                    intent_name: None,
+                    confidence_score: 0.2,
+                },
+                slots: vec![slot.clone()],
+            }],
+        };
+
+        // When
+        let byte_range = result.slot_range(&slot, OffsetUnit::Byte).unwrap();
+        let converted = result
+            .clone()
+            .with_converted_ranges(OffsetUnit::Char, OffsetUnit::Byte)
+            .unwrap();
+
+        // Then
+        assert_eq!(9..15, byte_range);
+        assert_eq!("東京", &input[byte_range]);
+        assert_eq!(9..15, converted.slots[0].range);
+        assert_eq!(9..15, converted.alternatives[0].slots[0].range);
+        assert_eq!(
+            SlotValue::Custom("東京".into()),
+            converted.slots[0].value.clone()
+        );
+        assert_eq!(
+            result,
+            converted
+                .with_converted_ranges(OffsetUnit::Byte, OffsetUnit::Char)
+                .unwrap()
+        );
+    }
+}
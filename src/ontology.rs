@@ -1,6 +1,21 @@
+#[cfg(feature = "chrono")]
+use crate::errors::*;
+use crate::migration::{FORMAT_VERSION, FORMAT_VERSION_KEY};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::cmp::Ordering;
 use std::ops::Range;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// Result of intent parsing
+///
+/// Its JSON representation carries a `formatVersion` marker, see the `migration` module.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "json_schema",
+    schemars(transform = crate::json_schema::add_format_version_property)
+)]
 pub struct IntentParserResult {
     pub input: String,
     pub intent: IntentClassifierResult,
@@ -9,26 +24,54 @@ pub struct IntentParserResult {
     pub alternatives: Vec<IntentParserAlternative>,
 }
 
+impl Serialize for IntentParserResult {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("IntentParserResult", 5)?;
+        state.serialize_field(FORMAT_VERSION_KEY, &FORMAT_VERSION)?;
+        state.serialize_field("input", &self.input)?;
+        state.serialize_field("intent", &self.intent)?;
+        state.serialize_field("slots", &self.slots)?;
+        state.serialize_field("alternatives", &self.alternatives)?;
+        state.end()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct IntentParserAlternative {
     pub intent: IntentClassifierResult,
     pub slots: Vec<Slot>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct IntentClassifierResult {
     pub intent_name: Option<String>,
     pub confidence_score: f32,
 }
 
+/// Compares two confidence scores, NaN being considered as the lowest confidence so that the
+/// order is total
+pub(crate) fn cmp_confidence_scores(a: f32, b: f32) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(&b),
+        (a_is_nan, b_is_nan) => b_is_nan.cmp(&a_is_nan),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Slot {
     pub raw_value: String,
     pub value: SlotValue,
     #[serde(default)]
     pub alternatives: Vec<SlotValue>,
+    /// Range of the raw value in the input, expressed in Unicode scalar values
     pub range: Range<usize>,
     pub entity: String,
     pub slot_name: String,
@@ -72,6 +115,7 @@ impl Slot {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(tag = "kind")]
 pub enum SlotValue {
     Custom(StringValue),
@@ -93,6 +137,7 @@ pub enum SlotValue {
 
 /// This struct is required in order to use serde Internally tagged enum representation
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct StringValue {
     pub value: String,
 }
@@ -112,21 +157,25 @@ impl From<&'static str> for StringValue {
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct NumberValue {
     pub value: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct OrdinalValue {
     pub value: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct PercentageValue {
     pub value: f64,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct InstantTimeValue {
     pub value: String,
     pub grain: Grain,
@@ -134,12 +183,77 @@ pub struct InstantTimeValue {
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct TimeIntervalValue {
     pub from: Option<String>,
     pub to: Option<String>,
 }
 
+/// Format of the datetime strings found in `InstantTimeValue` and `TimeIntervalValue`, e.g.
+/// "2017-06-13 18:00:00 +02:00"
+#[cfg(feature = "chrono")]
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_datetime(value: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_str(value, DATETIME_FORMAT).map_err(|e| {
+        OntologyError::conversion_failure(format!("Invalid datetime '{}': {}", value, e))
+    })
+}
+
+#[cfg(feature = "chrono")]
+fn format_datetime(datetime: &DateTime<FixedOffset>) -> String {
+    datetime.format(DATETIME_FORMAT).to_string()
+}
+
+#[cfg(feature = "chrono")]
+impl InstantTimeValue {
+    pub fn new_datetime(
+        datetime: DateTime<FixedOffset>,
+        grain: Grain,
+        precision: Precision,
+    ) -> InstantTimeValue {
+        InstantTimeValue {
+            value: format_datetime(&datetime),
+            grain,
+            precision,
+        }
+    }
+
+    /// Parses the string value into a typed datetime
+    pub fn datetime(&self) -> Result<DateTime<FixedOffset>> {
+        parse_datetime(&self.value)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeIntervalValue {
+    pub fn new_datetimes(
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
+    ) -> TimeIntervalValue {
+        TimeIntervalValue {
+            from: from.as_ref().map(format_datetime),
+            to: to.as_ref().map(format_datetime),
+        }
+    }
+
+    /// Parses the lower bound of the interval, if any, into a typed datetime
+    pub fn from_datetime(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        self.from
+            .as_ref()
+            .map(|from| parse_datetime(from))
+            .transpose()
+    }
+
+    /// Parses the upper bound of the interval, if any, into a typed datetime
+    pub fn to_datetime(&self) -> Result<Option<DateTime<FixedOffset>>> {
+        self.to.as_ref().map(|to| parse_datetime(to)).transpose()
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct AmountOfMoneyValue {
     pub value: f32,
     pub precision: Precision,
@@ -147,12 +261,14 @@ pub struct AmountOfMoneyValue {
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct TemperatureValue {
     pub value: f32,
     pub unit: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DurationValue {
     pub years: i64,
     pub quarters: i64,
@@ -166,6 +282,7 @@ pub struct DurationValue {
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum Grain {
     Year = 0,
     Quarter = 1,
@@ -178,6 +295,7 @@ pub enum Grain {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub enum Precision {
     Approximate,
     Exact,
@@ -240,4 +358,85 @@ mod tests {
         };
         assert_eq!(expected_result, deserialized);
     }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_datetime_round_trip_on_result_descriptions() {
+        use crate::entity::builtin_entity::BuiltinEntityKind;
+
+        for kind in BuiltinEntityKind::all() {
+            // Given
+            let values: Vec<SlotValue> = serde_json::from_str(&kind.result_description()).unwrap();
+
+            for value in values {
+                // When
+                let round_tripped = match value {
+                    SlotValue::InstantTime(ref instant) => {
+                        SlotValue::InstantTime(InstantTimeValue::new_datetime(
+                            instant.datetime().unwrap(),
+                            instant.grain,
+                            instant.precision,
+                        ))
+                    }
+                    SlotValue::TimeInterval(ref interval) => {
+                        SlotValue::TimeInterval(TimeIntervalValue::new_datetimes(
+                            interval.from_datetime().unwrap(),
+                            interval.to_datetime().unwrap(),
+                        ))
+                    }
+                    _ => continue,
+                };
+
+                // Then
+                assert_eq!(value, round_tripped);
+                assert_eq!(
+                    serde_json::to_string(&value).unwrap(),
+                    serde_json::to_string(&round_tripped).unwrap()
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_datetime_accessors() {
+        use chrono::Timelike;
+
+        // Given
+        let instant = InstantTimeValue {
+            value: "2017-06-13 18:00:00 +02:00".to_string(),
+            grain: Grain::Hour,
+            precision: Precision::Exact,
+        };
+        let interval = TimeIntervalValue {
+            from: None,
+            to: Some("2017-06-08 00:00:00 -05:30".to_string()),
+        };
+
+        // When
+        let datetime = instant.datetime().unwrap();
+        let from = interval.from_datetime().unwrap();
+        let to = interval.to_datetime().unwrap();
+
+        // Then
+        assert_eq!(18, datetime.hour());
+        assert_eq!(2 * 3600, datetime.offset().local_minus_utc());
+        assert_eq!(None, from);
+        let expected_to = DateTime::parse_from_rfc3339("2017-06-08T00:00:00-05:30").unwrap();
+        assert_eq!(Some(expected_to), to);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_invalid_datetime() {
+        // Given
+        let instant = InstantTimeValue {
+            value: "tomorrow".to_string(),
+            grain: Grain::Day,
+            precision: Precision::Exact,
+        };
+
+        // When/Then
+        assert!(instant.datetime().is_err());
+    }
 }
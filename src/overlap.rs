@@ -0,0 +1,322 @@
+//! Detection and resolution of overlapping slots and builtin entities
+//!
+//! Overlaps are resolved by keeping the preferred spans, according to a list of strategies in
+//! which each strategy breaks the ties of the previous ones. Remaining ties are broken by keeping
+//! the first span.
+
+use crate::dataset::EntityReference;
+use crate::entity::builtin_entity::BuiltinEntity;
+use crate::ontology::{cmp_confidence_scores, IntentParserAlternative, IntentParserResult, Slot};
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Part of an input, which may overlap with others
+pub trait Span {
+    fn range(&self) -> &Range<usize>;
+    fn confidence_score(&self) -> Option<f32>;
+    fn entity(&self) -> EntityReference;
+}
+
+impl Span for Slot {
+    fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    fn confidence_score(&self) -> Option<f32> {
+        self.confidence_score
+    }
+
+    fn entity(&self) -> EntityReference {
+        EntityReference::from_identifier(&self.entity)
+    }
+}
+
+impl Span for BuiltinEntity {
+    fn range(&self) -> &Range<usize> {
+        &self.range
+    }
+
+    fn confidence_score(&self) -> Option<f32> {
+        None
+    }
+
+    fn entity(&self) -> EntityReference {
+        EntityReference::Builtin(self.entity_kind)
+    }
+}
+
+/// Criterion used to pick a span among overlapping ones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlapStrategy {
+    LongestSpan,
+    /// Spans without confidence score are considered less confident than the others, and NaN
+    /// scores less confident than any other score
+    HighestConfidence,
+    BuiltinEntitiesFirst,
+    CustomEntitiesFirst,
+}
+
+impl OverlapStrategy {
+    /// Compares two spans, the preferred one being the lesser
+    fn compare<T: Span>(self, a: &T, b: &T) -> Ordering {
+        let is_builtin = |span: &T| matches!(span.entity(), EntityReference::Builtin(_));
+        match self {
+            OverlapStrategy::LongestSpan => range_length(b.range()).cmp(&range_length(a.range())),
+            OverlapStrategy::HighestConfidence => {
+                match (a.confidence_score(), b.confidence_score()) {
+                    (Some(a), Some(b)) => cmp_confidence_scores(b, a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                }
+            }
+            OverlapStrategy::BuiltinEntitiesFirst => is_builtin(b).cmp(&is_builtin(a)),
+            OverlapStrategy::CustomEntitiesFirst => is_builtin(a).cmp(&is_builtin(b)),
+        }
+    }
+}
+
+fn range_length(range: &Range<usize>) -> usize {
+    range.end.saturating_sub(range.start)
+}
+
+pub(crate) fn ranges_overlap(range: &Range<usize>, other: &Range<usize>) -> bool {
+    range.start < other.end && other.start < range.end
+}
+
+/// Returns the pairs of indices of the overlapping spans
+pub fn find_overlaps<T: Span>(spans: &[T]) -> Vec<(usize, usize)> {
+    let mut overlaps = vec![];
+    for (index, span) in spans.iter().enumerate() {
+        for (other_index, other_span) in spans.iter().enumerate().skip(index + 1) {
+            if ranges_overlap(span.range(), other_span.range()) {
+                overlaps.push((index, other_index))
+            }
+        }
+    }
+    overlaps
+}
+
+/// Returns the spans which are kept once overlaps are resolved, in their original order
+pub fn resolve_overlaps<T: Span + Clone>(spans: &[T], strategies: &[OverlapStrategy]) -> Vec<T> {
+    let mut preferred_indices = (0..spans.len()).collect::<Vec<_>>();
+    preferred_indices.sort_by(|a, b| {
+        strategies
+            .iter()
+            .map(|strategy| strategy.compare(&spans[*a], &spans[*b]))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut kept_indices: Vec<usize> = vec![];
+    for index in preferred_indices {
+        if !kept_indices
+            .iter()
+            .any(|kept_index| ranges_overlap(spans[*kept_index].range(), spans[index].range()))
+        {
+            kept_indices.push(index)
+        }
+    }
+    kept_indices.sort_unstable();
+    kept_indices
+        .into_iter()
+        .map(|index| spans[index].clone())
+        .collect()
+}
+
+impl IntentParserResult {
+    /// Returns whether some slots of the result, or of one of its alternatives, overlap
+    pub fn has_overlapping_slots(&self) -> bool {
+        !find_overlaps(&self.slots).is_empty()
+            || self
+                .alternatives
+                .iter()
+                .any(|alternative| !find_overlaps(&alternative.slots).is_empty())
+    }
+
+    /// Returns a copy of the result in which overlapping slots are resolved, for the result as
+    /// well as for its alternatives
+    pub fn resolve_overlapping_slots(&self, strategies: &[OverlapStrategy]) -> IntentParserResult {
+        IntentParserResult {
+            input: self.input.clone(),
+            intent: self.intent.clone(),
+            slots: resolve_overlaps(&self.slots, strategies),
+            alternatives: self
+                .alternatives
+                .iter()
+                .map(|alternative| IntentParserAlternative {
+                    intent: alternative.intent.clone(),
+                    slots: resolve_overlaps(&alternative.slots, strategies),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::builtin_entity::BuiltinEntityKind;
+    use crate::ontology::{
+        AmountOfMoneyValue, IntentClassifierResult, NumberValue, Precision, SlotValue,
+    };
+    use crate::test_utils::slot;
+
+    fn builtin_entities() -> Vec<BuiltinEntity> {
+        vec![
+            BuiltinEntity {
+                value: "10".to_string(),
+                range: 4..6,
+                entity: SlotValue::Number(NumberValue { value: 10. }),
+                alternatives: vec![],
+                entity_kind: BuiltinEntityKind::Number,
+            },
+            BuiltinEntity {
+                value: "10 dollars".to_string(),
+                range: 4..14,
+                entity: SlotValue::AmountOfMoney(AmountOfMoneyValue {
+                    value: 10.,
+                    precision: Precision::Exact,
+                    unit: Some("$".to_string()),
+                }),
+                alternatives: vec![],
+                entity_kind: BuiltinEntityKind::AmountOfMoney,
+            },
+            BuiltinEntity {
+                value: "2".to_string(),
+                range: 19..20,
+                entity: SlotValue::Number(NumberValue { value: 2. }),
+                alternatives: vec![],
+                entity_kind: BuiltinEntityKind::Number,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_find_overlaps() {
+        // Given
+        let slots = vec![
+            slot("slot", "snips/number", 4..6),
+            slot("slot", "snips/amountOfMoney", 4..14),
+            slot("slot", "beverage", 13..20),
+            slot("slot", "size", 20..25),
+        ];
+
+        // When
+        let overlaps = find_overlaps(&slots);
+
+        // Then
+        assert_eq!(vec![(0, 1), (1, 2)], overlaps);
+    }
+
+    #[test]
+    fn test_resolve_overlaps_of_builtin_entities() {
+        // Given
+        let entities = builtin_entities();
+
+        // When
+        let resolved_entities = resolve_overlaps(&entities, &[OverlapStrategy::LongestSpan]);
+
+        // Then
+        assert_eq!(
+            vec![entities[1].clone(), entities[2].clone()],
+            resolved_entities
+        );
+    }
+
+    #[test]
+    fn test_resolve_overlaps_with_strategies() {
+        // Given
+        let slots = vec![
+            Slot {
+                confidence_score: Some(0.4),
+                ..slot("slot", "beverage", 0..12)
+            },
+            Slot {
+                confidence_score: Some(0.9),
+                ..slot("slot", "snips/number", 4..6)
+            },
+            slot("slot", "snips/amountOfMoney", 4..14),
+        ];
+
+        // When/Then
+        assert_eq!(
+            vec![slots[0].clone()],
+            resolve_overlaps(&slots, &[OverlapStrategy::LongestSpan])
+        );
+        assert_eq!(
+            vec![slots[1].clone()],
+            resolve_overlaps(&slots, &[OverlapStrategy::HighestConfidence])
+        );
+        assert_eq!(
+            vec![slots[0].clone()],
+            resolve_overlaps(&slots, &[OverlapStrategy::CustomEntitiesFirst])
+        );
+        assert_eq!(
+            vec![slots[2].clone()],
+            resolve_overlaps(
+                &slots,
+                &[
+                    OverlapStrategy::BuiltinEntitiesFirst,
+                    OverlapStrategy::LongestSpan
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_nan_confidence_score_is_the_lowest() {
+        // Given
+        let slots = vec![
+            Slot {
+                confidence_score: Some(f32::NAN),
+                ..slot("slot", "beverage", 0..12)
+            },
+            Slot {
+                confidence_score: Some(0.1),
+                ..slot("slot", "snips/number", 4..6)
+            },
+            slot("slot", "snips/amountOfMoney", 4..14),
+        ];
+
+        // When
+        let resolved_slots = resolve_overlaps(&slots, &[OverlapStrategy::HighestConfidence]);
+
+        // Then
+        assert_eq!(1, resolved_slots.len());
+        assert_eq!("snips/number", resolved_slots[0].entity);
+    }
+
+    #[test]
+    fn test_resolve_overlapping_slots() {
+        // Given
+        let result = IntentParserResult {
+            input: "pay 10 dollars for 2 lattes".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("pay".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: vec![
+                slot("slot", "snips/number", 4..6),
+                slot("slot", "snips/amountOfMoney", 4..14),
+                slot("slot", "snips/number", 19..20),
+            ],
+            alternatives: vec![IntentParserAlternative {
+                intent: IntentClassifierResult {
+                    intent_name: None,
+                    confidence_score: 0.2,
+                },
+                slots: vec![],
+            }],
+        };
+
+        // When
+        let resolved_result = result.resolve_overlapping_slots(&[OverlapStrategy::LongestSpan]);
+
+        // Then
+        assert!(result.has_overlapping_slots());
+        assert!(!resolved_result.has_overlapping_slots());
+        assert_eq!(
+            vec![result.slots[1].clone(), result.slots[2].clone()],
+            resolved_result.slots
+        );
+        assert_eq!(result.alternatives, resolved_result.alternatives);
+    }
+}
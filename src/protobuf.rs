@@ -0,0 +1,479 @@
+//! Protocol Buffers representation of the ontology, available with the `protobuf` feature
+//!
+//! The messages are generated at build time from `proto/snips_nlu_ontology.proto` and live in
+//! the `proto` module. Conversions to protobuf messages are infallible, while conversions from
+//! protobuf messages fail when a required field is missing, when an enum value is unknown or
+//! unspecified, or when a range does not fit in a `usize`.
+
+use crate::errors::*;
+use crate::ontology::*;
+use std::convert::{TryFrom, TryInto};
+use std::ops::Range;
+
+/// Messages generated from `proto/snips_nlu_ontology.proto`
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/snips_nlu_ontology.rs"));
+}
+
+fn required<T>(field: Option<T>, name: &str) -> Result<T> {
+    field.ok_or_else(|| {
+        OntologyError::conversion_failure(format!("Missing required protobuf field '{}'", name))
+    })
+}
+
+impl From<IntentParserResult> for proto::IntentParserResult {
+    fn from(result: IntentParserResult) -> Self {
+        Self {
+            input: result.input,
+            intent: Some(result.intent.into()),
+            slots: result.slots.into_iter().map(Into::into).collect(),
+            alternatives: result.alternatives.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::IntentParserResult> for IntentParserResult {
+    type Error = OntologyError;
+
+    fn try_from(result: proto::IntentParserResult) -> Result<Self> {
+        Ok(Self {
+            input: result.input,
+            intent: required(result.intent, "intent")?.into(),
+            slots: result
+                .slots
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            alternatives: result
+                .alternatives
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl From<IntentParserAlternative> for proto::IntentParserAlternative {
+    fn from(alternative: IntentParserAlternative) -> Self {
+        Self {
+            intent: Some(alternative.intent.into()),
+            slots: alternative.slots.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::IntentParserAlternative> for IntentParserAlternative {
+    type Error = OntologyError;
+
+    fn try_from(alternative: proto::IntentParserAlternative) -> Result<Self> {
+        Ok(Self {
+            intent: required(alternative.intent, "intent")?.into(),
+            slots: alternative
+                .slots
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl From<IntentClassifierResult> for proto::IntentClassifierResult {
+    fn from(result: IntentClassifierResult) -> Self {
+        Self {
+            intent_name: result.intent_name,
+            confidence_score: result.confidence_score,
+        }
+    }
+}
+
+impl From<proto::IntentClassifierResult> for IntentClassifierResult {
+    fn from(result: proto::IntentClassifierResult) -> Self {
+        Self {
+            intent_name: result.intent_name,
+            confidence_score: result.confidence_score,
+        }
+    }
+}
+
+impl From<Range<usize>> for proto::Range {
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            start: range.start as u64,
+            end: range.end as u64,
+        }
+    }
+}
+
+impl TryFrom<proto::Range> for Range<usize> {
+    type Error = OntologyError;
+
+    fn try_from(range: proto::Range) -> Result<Self> {
+        let convert = |offset: u64| {
+            usize::try_from(offset).map_err(|_| {
+                OntologyError::conversion_failure(format!(
+                    "Range offset {} does not fit in a usize",
+                    offset
+                ))
+            })
+        };
+        Ok(convert(range.start)?..convert(range.end)?)
+    }
+}
+
+impl From<Slot> for proto::Slot {
+    fn from(slot: Slot) -> Self {
+        Self {
+            raw_value: slot.raw_value,
+            value: Some(slot.value.into()),
+            alternatives: slot.alternatives.into_iter().map(Into::into).collect(),
+            range: Some(slot.range.into()),
+            entity: slot.entity,
+            slot_name: slot.slot_name,
+            confidence_score: slot.confidence_score,
+        }
+    }
+}
+
+impl TryFrom<proto::Slot> for Slot {
+    type Error = OntologyError;
+
+    fn try_from(slot: proto::Slot) -> Result<Self> {
+        Ok(Self {
+            raw_value: slot.raw_value,
+            value: required(slot.value, "value")?.try_into()?,
+            alternatives: slot
+                .alternatives
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            range: required(slot.range, "range")?.try_into()?,
+            entity: slot.entity,
+            slot_name: slot.slot_name,
+            confidence_score: slot.confidence_score,
+        })
+    }
+}
+
+impl From<SlotValue> for proto::SlotValue {
+    fn from(value: SlotValue) -> Self {
+        use self::proto::slot_value::Value;
+        let value = match value {
+            SlotValue::Custom(v) => Value::Custom(v.into()),
+            SlotValue::Number(v) => Value::Number(v.into()),
+            SlotValue::Ordinal(v) => Value::Ordinal(v.into()),
+            SlotValue::Percentage(v) => Value::Percentage(v.into()),
+            SlotValue::InstantTime(v) => Value::InstantTime(v.into()),
+            SlotValue::TimeInterval(v) => Value::TimeInterval(v.into()),
+            SlotValue::AmountOfMoney(v) => Value::AmountOfMoney(v.into()),
+            SlotValue::Temperature(v) => Value::Temperature(v.into()),
+            SlotValue::Duration(v) => Value::Duration(v.into()),
+            SlotValue::MusicAlbum(v) => Value::MusicAlbum(v.into()),
+            SlotValue::MusicArtist(v) => Value::MusicArtist(v.into()),
+            SlotValue::MusicTrack(v) => Value::MusicTrack(v.into()),
+            SlotValue::City(v) => Value::City(v.into()),
+            SlotValue::Country(v) => Value::Country(v.into()),
+            SlotValue::Region(v) => Value::Region(v.into()),
+        };
+        Self { value: Some(value) }
+    }
+}
+
+impl TryFrom<proto::SlotValue> for SlotValue {
+    type Error = OntologyError;
+
+    fn try_from(value: proto::SlotValue) -> Result<Self> {
+        use self::proto::slot_value::Value;
+        Ok(match required(value.value, "value")? {
+            Value::Custom(v) => SlotValue::Custom(v.into()),
+            Value::Number(v) => SlotValue::Number(v.into()),
+            Value::Ordinal(v) => SlotValue::Ordinal(v.into()),
+            Value::Percentage(v) => SlotValue::Percentage(v.into()),
+            Value::InstantTime(v) => SlotValue::InstantTime(v.try_into()?),
+            Value::TimeInterval(v) => SlotValue::TimeInterval(v.into()),
+            Value::AmountOfMoney(v) => SlotValue::AmountOfMoney(v.try_into()?),
+            Value::Temperature(v) => SlotValue::Temperature(v.into()),
+            Value::Duration(v) => SlotValue::Duration(v.try_into()?),
+            Value::MusicAlbum(v) => SlotValue::MusicAlbum(v.into()),
+            Value::MusicArtist(v) => SlotValue::MusicArtist(v.into()),
+            Value::MusicTrack(v) => SlotValue::MusicTrack(v.into()),
+            Value::City(v) => SlotValue::City(v.into()),
+            Value::Country(v) => SlotValue::Country(v.into()),
+            Value::Region(v) => SlotValue::Region(v.into()),
+        })
+    }
+}
+
+/// Implements the conversions of value types made of fields which map one to one
+macro_rules! impl_value_conversions {
+    ($($type:ident { $($field:ident),* }),*) => {
+        $(
+            impl From<$type> for proto::$type {
+                fn from(value: $type) -> Self {
+                    Self { $($field: value.$field),* }
+                }
+            }
+
+            impl From<proto::$type> for $type {
+                fn from(value: proto::$type) -> Self {
+                    Self { $($field: value.$field),* }
+                }
+            }
+        )*
+    };
+}
+
+impl_value_conversions!(
+    StringValue { value },
+    NumberValue { value },
+    OrdinalValue { value },
+    PercentageValue { value },
+    TimeIntervalValue { from, to },
+    TemperatureValue { value, unit }
+);
+
+impl From<InstantTimeValue> for proto::InstantTimeValue {
+    fn from(value: InstantTimeValue) -> Self {
+        Self {
+            value: value.value,
+            grain: proto::Grain::from(value.grain).into(),
+            precision: proto::Precision::from(value.precision).into(),
+        }
+    }
+}
+
+impl TryFrom<proto::InstantTimeValue> for InstantTimeValue {
+    type Error = OntologyError;
+
+    fn try_from(value: proto::InstantTimeValue) -> Result<Self> {
+        Ok(Self {
+            value: value.value,
+            grain: grain_from_proto(value.grain)?,
+            precision: precision_from_proto(value.precision)?,
+        })
+    }
+}
+
+impl From<AmountOfMoneyValue> for proto::AmountOfMoneyValue {
+    fn from(value: AmountOfMoneyValue) -> Self {
+        Self {
+            value: value.value,
+            precision: proto::Precision::from(value.precision).into(),
+            unit: value.unit,
+        }
+    }
+}
+
+impl TryFrom<proto::AmountOfMoneyValue> for AmountOfMoneyValue {
+    type Error = OntologyError;
+
+    fn try_from(value: proto::AmountOfMoneyValue) -> Result<Self> {
+        Ok(Self {
+            value: value.value,
+            precision: precision_from_proto(value.precision)?,
+            unit: value.unit,
+        })
+    }
+}
+
+impl From<DurationValue> for proto::DurationValue {
+    fn from(value: DurationValue) -> Self {
+        Self {
+            years: value.years,
+            quarters: value.quarters,
+            months: value.months,
+            weeks: value.weeks,
+            days: value.days,
+            hours: value.hours,
+            minutes: value.minutes,
+            seconds: value.seconds,
+            precision: proto::Precision::from(value.precision).into(),
+        }
+    }
+}
+
+impl TryFrom<proto::DurationValue> for DurationValue {
+    type Error = OntologyError;
+
+    fn try_from(value: proto::DurationValue) -> Result<Self> {
+        Ok(Self {
+            years: value.years,
+            quarters: value.quarters,
+            months: value.months,
+            weeks: value.weeks,
+            days: value.days,
+            hours: value.hours,
+            minutes: value.minutes,
+            seconds: value.seconds,
+            precision: precision_from_proto(value.precision)?,
+        })
+    }
+}
+
+impl From<Grain> for proto::Grain {
+    fn from(grain: Grain) -> Self {
+        match grain {
+            Grain::Year => proto::Grain::Year,
+            Grain::Quarter => proto::Grain::Quarter,
+            Grain::Month => proto::Grain::Month,
+            Grain::Week => proto::Grain::Week,
+            Grain::Day => proto::Grain::Day,
+            Grain::Hour => proto::Grain::Hour,
+            Grain::Minute => proto::Grain::Minute,
+            Grain::Second => proto::Grain::Second,
+        }
+    }
+}
+
+impl TryFrom<proto::Grain> for Grain {
+    type Error = OntologyError;
+
+    fn try_from(grain: proto::Grain) -> Result<Self> {
+        Ok(match grain {
+            proto::Grain::Unspecified => {
+                return Err(OntologyError::conversion_failure(
+                    "Unspecified protobuf grain",
+                ))
+            }
+            proto::Grain::Year => Grain::Year,
+            proto::Grain::Quarter => Grain::Quarter,
+            proto::Grain::Month => Grain::Month,
+            proto::Grain::Week => Grain::Week,
+            proto::Grain::Day => Grain::Day,
+            proto::Grain::Hour => Grain::Hour,
+            proto::Grain::Minute => Grain::Minute,
+            proto::Grain::Second => Grain::Second,
+        })
+    }
+}
+
+impl From<Precision> for proto::Precision {
+    fn from(precision: Precision) -> Self {
+        match precision {
+            Precision::Approximate => proto::Precision::Approximate,
+            Precision::Exact => proto::Precision::Exact,
+        }
+    }
+}
+
+impl TryFrom<proto::Precision> for Precision {
+    type Error = OntologyError;
+
+    fn try_from(precision: proto::Precision) -> Result<Self> {
+        Ok(match precision {
+            proto::Precision::Unspecified => {
+                return Err(OntologyError::conversion_failure(
+                    "Unspecified protobuf precision",
+                ))
+            }
+            proto::Precision::Approximate => Precision::Approximate,
+            proto::Precision::Exact => Precision::Exact,
+        })
+    }
+}
+
+fn grain_from_proto(grain: i32) -> Result<Grain> {
+    proto::Grain::try_from(grain)
+        .map_err(|_| {
+            OntologyError::conversion_failure(format!("Unknown protobuf grain {}", grain))
+        })?
+        .try_into()
+}
+
+fn precision_from_proto(precision: i32) -> Result<Precision> {
+    proto::Precision::try_from(precision)
+        .map_err(|_| {
+            OntologyError::conversion_failure(format!("Unknown protobuf precision {}", precision))
+        })?
+        .try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::all_slot_values;
+    use prost::Message;
+
+    #[test]
+    fn test_intent_parser_result_round_trip() {
+        // Given
+        let slots = all_slot_values()
+            .into_iter()
+            .map(|value| Slot {
+                raw_value: "value".to_string(),
+                value,
+                alternatives: vec![SlotValue::Number(NumberValue { value: 3. })],
+                range: 2..7,
+                entity: "entity".to_string(),
+                slot_name: "slot_name".to_string(),
+                confidence_score: Some(0.7),
+            })
+            .collect::<Vec<_>>();
+        let result = IntentParserResult {
+            input: "a value".to_string(),
+            intent: IntentClassifierResult {
+                intent_name: Some("intent_name".to_string()),
+                confidence_score: 0.8,
+            },
+            slots: slots.clone(),
+            alternatives: vec![IntentParserAlternative {
+                intent: IntentClassifierResult {
+                    intent_name: None,
+                    confidence_score: 0.2,
+                },
+                slots,
+            }],
+        };
+
+        // When
+        let bytes = proto::IntentParserResult::from(result.clone()).encode_to_vec();
+        let decoded = proto::IntentParserResult::decode(bytes.as_slice()).unwrap();
+
+        // Then
+        assert_eq!(result, IntentParserResult::try_from(decoded).unwrap());
+    }
+
+    #[test]
+    fn test_grain_round_trip() {
+        for grain in &[
+            Grain::Year,
+            Grain::Quarter,
+            Grain::Month,
+            Grain::Week,
+            Grain::Day,
+            Grain::Hour,
+            Grain::Minute,
+            Grain::Second,
+        ] {
+            assert_eq!(*grain, Grain::try_from(proto::Grain::from(*grain)).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_invalid_messages_are_rejected() {
+        // Given
+        let missing_value = proto::Slot {
+            raw_value: "value".to_string(),
+            value: None,
+            alternatives: vec![],
+            range: Some(proto::Range { start: 0, end: 5 }),
+            entity: "entity".to_string(),
+            slot_name: "slot_name".to_string(),
+            confidence_score: None,
+        };
+        let unspecified_precision = proto::AmountOfMoneyValue {
+            value: 3.,
+            precision: proto::Precision::Unspecified.into(),
+            unit: None,
+        };
+        let unknown_grain = proto::InstantTimeValue {
+            value: "2017-06-13 18:00:00 +02:00".to_string(),
+            grain: 42,
+            precision: proto::Precision::Exact.into(),
+        };
+
+        // When/Then
+        assert!(Slot::try_from(missing_value).is_err());
+        assert!(AmountOfMoneyValue::try_from(unspecified_precision).is_err());
+        assert!(InstantTimeValue::try_from(unknown_grain).is_err());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::errors::*;
+use crate::ontology::TemperatureValue;
+
+/// Unit of a `TemperatureValue`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    /// Plain degrees, when the scale was not specified
+    Degree,
+}
+
+impl TemperatureUnit {
+    pub fn all() -> &'static [TemperatureUnit] {
+        static ALL: &[TemperatureUnit] = &[
+            TemperatureUnit::Celsius,
+            TemperatureUnit::Fahrenheit,
+            TemperatureUnit::Kelvin,
+            TemperatureUnit::Degree,
+        ];
+        ALL
+    }
+
+    /// Value of the `unit` string of a `TemperatureValue` expressed in this unit
+    pub fn identifier(&self) -> Option<&'static str> {
+        match *self {
+            TemperatureUnit::Celsius => Some("celsius"),
+            TemperatureUnit::Fahrenheit => Some("fahrenheit"),
+            TemperatureUnit::Kelvin => Some("kelvin"),
+            TemperatureUnit::Degree => None,
+        }
+    }
+
+    /// Parses the `unit` string of a `TemperatureValue`, a missing unit meaning plain degrees
+    pub fn from_unit(unit: Option<&str>) -> Result<Self> {
+        let unit = match unit {
+            Some(unit) => unit.trim().to_lowercase(),
+            None => return Ok(TemperatureUnit::Degree),
+        };
+        Ok(match &*unit {
+            "celsius" | "°c" | "c" => TemperatureUnit::Celsius,
+            "fahrenheit" | "°f" | "f" => TemperatureUnit::Fahrenheit,
+            "kelvin" | "k" => TemperatureUnit::Kelvin,
+            "degree" | "degrees" | "°" => TemperatureUnit::Degree,
+            _ => {
+                return Err(OntologyError::conversion_failure(format!(
+                    "Unknown temperature unit: {}",
+                    unit
+                )))
+            }
+        })
+    }
+
+    fn value_to_kelvin(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value + 273.15,
+            TemperatureUnit::Fahrenheit => (value - 32.) * 5. / 9. + 273.15,
+            TemperatureUnit::Kelvin | TemperatureUnit::Degree => value,
+        }
+    }
+
+    fn value_from_kelvin(self, value: f64) -> f64 {
+        match self {
+            TemperatureUnit::Celsius => value - 273.15,
+            TemperatureUnit::Fahrenheit => (value - 273.15) * 9. / 5. + 32.,
+            TemperatureUnit::Kelvin | TemperatureUnit::Degree => value,
+        }
+    }
+}
+
+impl TemperatureValue {
+    pub fn new_with_unit(value: f32, unit: TemperatureUnit) -> TemperatureValue {
+        TemperatureValue {
+            value,
+            unit: unit.identifier().map(|unit| unit.to_string()),
+        }
+    }
+
+    /// Parses the unit string of the temperature
+    pub fn temperature_unit(&self) -> Result<TemperatureUnit> {
+        TemperatureUnit::from_unit(self.unit.as_deref())
+    }
+
+    /// Converts the temperature to another unit
+    ///
+    /// Temperatures in plain degrees cannot be converted, since their scale is unknown.
+    pub fn convert_to(&self, unit: TemperatureUnit) -> Result<TemperatureValue> {
+        let current_unit = self.temperature_unit()?;
+        if current_unit == unit {
+            return Ok(TemperatureValue::new_with_unit(self.value, unit));
+        }
+        if current_unit == TemperatureUnit::Degree || unit == TemperatureUnit::Degree {
+            return Err(OntologyError::conversion_failure(format!(
+                "Cannot convert a temperature from {:?} to {:?}",
+                current_unit, unit
+            )));
+        }
+        let kelvin = current_unit.value_to_kelvin(f64::from(self.value));
+        Ok(TemperatureValue::new_with_unit(
+            unit.value_from_kelvin(kelvin) as f32,
+            unit,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 1e-3,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_temperature_unit() {
+        assert_eq!(
+            TemperatureUnit::Celsius,
+            TemperatureValue::new_with_unit(23., TemperatureUnit::Celsius)
+                .temperature_unit()
+                .unwrap()
+        );
+        assert_eq!(
+            TemperatureUnit::Fahrenheit,
+            TemperatureUnit::from_unit(Some("°F")).unwrap()
+        );
+        assert_eq!(
+            TemperatureUnit::Degree,
+            TemperatureUnit::from_unit(None).unwrap()
+        );
+        assert!(TemperatureUnit::from_unit(Some("rankine")).is_err());
+    }
+
+    #[test]
+    fn test_convert_to() {
+        // Given
+        let celsius = TemperatureValue::new_with_unit(23., TemperatureUnit::Celsius);
+        let fahrenheit = TemperatureValue::new_with_unit(60., TemperatureUnit::Fahrenheit);
+        let degrees = TemperatureValue {
+            value: 20.,
+            unit: None,
+        };
+
+        // When
+        let celsius_to_fahrenheit = celsius.convert_to(TemperatureUnit::Fahrenheit).unwrap();
+        let fahrenheit_to_kelvin = fahrenheit.convert_to(TemperatureUnit::Kelvin).unwrap();
+        let kelvin_to_celsius = fahrenheit_to_kelvin
+            .convert_to(TemperatureUnit::Celsius)
+            .unwrap();
+
+        // Then
+        assert_eq!(Some("fahrenheit".to_string()), celsius_to_fahrenheit.unit);
+        assert_close(73.4, celsius_to_fahrenheit.value);
+        assert_eq!(Some("kelvin".to_string()), fahrenheit_to_kelvin.unit);
+        assert_close(288.706, fahrenheit_to_kelvin.value);
+        assert_close(15.556, kelvin_to_celsius.value);
+        assert_eq!(
+            degrees,
+            degrees.convert_to(TemperatureUnit::Degree).unwrap()
+        );
+        assert!(degrees.convert_to(TemperatureUnit::Celsius).is_err());
+        assert!(celsius.convert_to(TemperatureUnit::Degree).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Fixtures shared by the unit tests
+
+use crate::ontology::*;
+use std::ops::Range;
+
+/// Slot of a custom entity, whose raw value and value are both "value"
+pub fn slot(slot_name: &str, entity: &str, range: Range<usize>) -> Slot {
+    Slot {
+        raw_value: "value".to_string(),
+        value: SlotValue::Custom("value".into()),
+        alternatives: vec![],
+        range,
+        entity: entity.to_string(),
+        slot_name: slot_name.to_string(),
+        confidence_score: None,
+    }
+}
+
+pub fn intent(intent_name: Option<&str>, confidence_score: f32) -> IntentClassifierResult {
+    IntentClassifierResult {
+        intent_name: intent_name.map(|name| name.to_string()),
+        confidence_score,
+    }
+}
+
+/// One value of each kind of slot value
+#[cfg(any(
+    feature = "bincode",
+    feature = "cbor",
+    feature = "messagepack",
+    feature = "protobuf"
+))]
+pub fn all_slot_values() -> Vec<SlotValue> {
+    vec![
+        SlotValue::Custom("custom".into()),
+        SlotValue::Number(NumberValue { value: 42.5 }),
+        SlotValue::Ordinal(OrdinalValue { value: -2 }),
+        SlotValue::Percentage(PercentageValue { value: 20. }),
+        SlotValue::InstantTime(InstantTimeValue {
+            value: "2017-06-13 18:00:00 +02:00".to_string(),
+            grain: Grain::Quarter,
+            precision: Precision::Approximate,
+        }),
+        SlotValue::TimeInterval(TimeIntervalValue {
+            from: None,
+            to: Some("2017-06-07 18:00:00 +02:00".to_string()),
+        }),
+        SlotValue::AmountOfMoney(AmountOfMoneyValue {
+            value: 10.05,
+            precision: Precision::Exact,
+            unit: Some("€".to_string()),
+        }),
+        SlotValue::Temperature(TemperatureValue {
+            value: -3.5,
+            unit: None,
+        }),
+        SlotValue::Duration(DurationValue {
+            years: 1,
+            quarters: 2,
+            months: 3,
+            weeks: 4,
+            days: 5,
+            hours: 6,
+            minutes: 7,
+            seconds: 8,
+            precision: Precision::Exact,
+        }),
+        SlotValue::MusicAlbum("Discovery".into()),
+        SlotValue::MusicArtist("Daft Punk".into()),
+        SlotValue::MusicTrack("Harder Better Faster Stronger".into()),
+        SlotValue::City("Paris".into()),
+        SlotValue::Country("France".into()),
+        SlotValue::Region("California".into()),
+    ]
+}
@@ -0,0 +1,387 @@
+//! Interval algebra on `TimeIntervalValue`
+//!
+//! Intervals are half-open: the `from` bound is included while the `to` bound is excluded, which
+//! matches the way intervals such as "tonight" are resolved (from 18:00 to midnight). A missing
+//! bound means the interval is unbounded on that side, so an interval with no bound at all spans
+//! the whole timeline.
+
+use crate::errors::*;
+use crate::ontology::{InstantTimeValue, TimeIntervalValue};
+use chrono::{DateTime, Duration, FixedOffset};
+use std::cmp::Ordering;
+
+type Bound = Option<DateTime<FixedOffset>>;
+
+/// Compares two lower bounds, `None` standing for minus infinity
+fn cmp_from(a: &Bound, b: &Bound) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
+
+/// Compares two upper bounds, `None` standing for plus infinity
+fn cmp_to(a: &Bound, b: &Bound) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    }
+}
+
+/// Compares a lower bound with an upper bound, a missing lower bound standing for minus infinity
+/// and a missing upper bound for plus infinity
+fn cmp_from_to(from: &Bound, to: &Bound) -> Ordering {
+    match (from, to) {
+        (Some(from), Some(to)) => from.cmp(to),
+        _ => Ordering::Less,
+    }
+}
+
+/// Returns whether the interval starting at `from` and ending at `to` contains at least one
+/// instant
+fn is_non_empty(from: &Bound, to: &Bound) -> bool {
+    match (from, to) {
+        (Some(from), Some(to)) => from < to,
+        _ => true,
+    }
+}
+
+impl TimeIntervalValue {
+    fn bounds(&self) -> Result<(Bound, Bound)> {
+        Ok((self.from_datetime()?, self.to_datetime()?))
+    }
+
+    /// Checks that both bounds can be parsed and that `from` is not after `to`
+    pub fn validate(&self) -> Result<()> {
+        if let (Some(from), Some(to)) = self.bounds()? {
+            if from > to {
+                return Err(OntologyError::InvalidTimeInterval {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the interval contains no instant, which happens when `from` equals `to`
+    pub fn is_empty(&self) -> Result<bool> {
+        let (from, to) = self.bounds()?;
+        Ok(!is_non_empty(&from, &to))
+    }
+
+    /// Returns whether `datetime` falls inside the interval
+    pub fn contains_datetime(&self, datetime: &DateTime<FixedOffset>) -> Result<bool> {
+        let (from, to) = self.bounds()?;
+        let after_from = from.map(|from| from <= *datetime).unwrap_or(true);
+        let before_to = to.map(|to| *datetime < to).unwrap_or(true);
+        Ok(after_from && before_to)
+    }
+
+    /// Returns whether the instant falls inside the interval
+    ///
+    /// The instant is considered as a point in time, its grain is not taken into account.
+    pub fn contains(&self, instant: &InstantTimeValue) -> Result<bool> {
+        self.contains_datetime(&instant.datetime()?)
+    }
+
+    /// Returns whether the two intervals share at least one instant
+    pub fn overlaps(&self, other: &TimeIntervalValue) -> Result<bool> {
+        Ok(self.intersection(other)?.is_some())
+    }
+
+    /// Returns the instants shared by both intervals, or `None` when they are disjoint
+    pub fn intersection(&self, other: &TimeIntervalValue) -> Result<Option<TimeIntervalValue>> {
+        let (self_from, self_to) = self.bounds()?;
+        let (other_from, other_to) = other.bounds()?;
+        let (from, from_bound) = if cmp_from(&self_from, &other_from) == Ordering::Less {
+            (&other.from, other_from)
+        } else {
+            (&self.from, self_from)
+        };
+        let (to, to_bound) = if cmp_to(&self_to, &other_to) == Ordering::Greater {
+            (&other.to, other_to)
+        } else {
+            (&self.to, self_to)
+        };
+        if !is_non_empty(&from_bound, &to_bound) {
+            return Ok(None);
+        }
+        Ok(Some(TimeIntervalValue {
+            from: from.clone(),
+            to: to.clone(),
+        }))
+    }
+
+    /// Returns the interval covering both intervals, or `None` when they neither overlap nor
+    /// touch each other, as the result would not be a single interval
+    ///
+    /// An empty interval adds no instant, hence the union with it is the other interval.
+    pub fn union(&self, other: &TimeIntervalValue) -> Result<Option<TimeIntervalValue>> {
+        let (self_from, self_to) = self.bounds()?;
+        let (other_from, other_to) = other.bounds()?;
+        if !is_non_empty(&self_from, &self_to) {
+            return Ok(Some(other.clone()));
+        }
+        if !is_non_empty(&other_from, &other_to) {
+            return Ok(Some(self.clone()));
+        }
+        let touching = cmp_from_to(&self_from, &other_to) != Ordering::Greater
+            && cmp_from_to(&other_from, &self_to) != Ordering::Greater;
+        if !touching {
+            return Ok(None);
+        }
+        let from = if cmp_from(&self_from, &other_from) == Ordering::Greater {
+            &other.from
+        } else {
+            &self.from
+        };
+        let to = if cmp_to(&self_to, &other_to) == Ordering::Less {
+            &other.to
+        } else {
+            &self.to
+        };
+        Ok(Some(TimeIntervalValue {
+            from: from.clone(),
+            to: to.clone(),
+        }))
+    }
+
+    /// Returns the duration of the interval, or `None` when the interval is open-ended
+    pub fn duration(&self) -> Result<Option<Duration>> {
+        self.validate()?;
+        Ok(match self.bounds()? {
+            (Some(from), Some(to)) => Some(to.signed_duration_since(from)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::{Grain, Precision};
+
+    fn interval(from: Option<&str>, to: Option<&str>) -> TimeIntervalValue {
+        TimeIntervalValue {
+            from: from.map(|s| s.to_string()),
+            to: to.map(|s| s.to_string()),
+        }
+    }
+
+    fn instant(value: &str) -> InstantTimeValue {
+        InstantTimeValue {
+            value: value.to_string(),
+            grain: Grain::Hour,
+            precision: Precision::Exact,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(interval(
+            Some("2017-06-07 18:00:00 +02:00"),
+            Some("2017-06-08 00:00:00 +02:00")
+        )
+        .validate()
+        .is_ok());
+        assert!(interval(None, Some("2017-06-08 00:00:00 +02:00"))
+            .validate()
+            .is_ok());
+        assert!(interval(
+            Some("2017-06-08 00:00:00 +02:00"),
+            Some("2017-06-07 18:00:00 +02:00")
+        )
+        .validate()
+        .is_err());
+        assert_eq!(
+            Err(OntologyError::InvalidTimeInterval {
+                from: "2017-06-08 00:00:00 +02:00".to_string(),
+                to: "2017-06-07 18:00:00 +02:00".to_string(),
+            }),
+            interval(
+                Some("2017-06-08 00:00:00 +02:00"),
+                Some("2017-06-07 18:00:00 +02:00")
+            )
+            .validate()
+        );
+        assert!(interval(Some("tonight"), None).validate().is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        // Given
+        let tonight = interval(
+            Some("2017-06-07 18:00:00 +02:00"),
+            Some("2017-06-08 00:00:00 +02:00"),
+        );
+        let from_tonight = interval(Some("2017-06-07 18:00:00 +02:00"), None);
+
+        // When/Then
+        assert!(tonight
+            .contains(&instant("2017-06-07 18:00:00 +02:00"))
+            .unwrap());
+        assert!(tonight
+            .contains(&instant("2017-06-07 20:00:00 +00:00"))
+            .unwrap());
+        assert!(!tonight
+            .contains(&instant("2017-06-08 00:00:00 +02:00"))
+            .unwrap());
+        assert!(!tonight
+            .contains(&instant("2017-06-07 17:00:00 +02:00"))
+            .unwrap());
+        assert!(from_tonight
+            .contains(&instant("2030-01-01 00:00:00 +02:00"))
+            .unwrap());
+        assert!(interval(None, None)
+            .contains(&instant("2017-06-07 17:00:00 +02:00"))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_intersection() {
+        // Given
+        let weekend = interval(
+            Some("2017-06-10 00:00:00 +02:00"),
+            Some("2017-06-12 00:00:00 +02:00"),
+        );
+        let evening = interval(
+            Some("2017-06-10 18:00:00 +02:00"),
+            Some("2017-06-11 00:00:00 +02:00"),
+        );
+        let before_weekend = interval(None, Some("2017-06-10 00:00:00 +02:00"));
+        let after_saturday_noon = interval(Some("2017-06-10 12:00:00 +02:00"), None);
+
+        // When/Then
+        assert_eq!(
+            Some(evening.clone()),
+            weekend.intersection(&evening).unwrap()
+        );
+        assert_eq!(None, weekend.intersection(&before_weekend).unwrap());
+        assert!(!weekend.overlaps(&before_weekend).unwrap());
+        assert_eq!(
+            Some(interval(
+                Some("2017-06-10 12:00:00 +02:00"),
+                Some("2017-06-12 00:00:00 +02:00")
+            )),
+            weekend.intersection(&after_saturday_noon).unwrap()
+        );
+        assert_eq!(
+            None,
+            before_weekend.intersection(&after_saturday_noon).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        // Given
+        let saturday = interval(
+            Some("2017-06-10 00:00:00 +02:00"),
+            Some("2017-06-11 00:00:00 +02:00"),
+        );
+        let sunday = interval(
+            Some("2017-06-11 00:00:00 +02:00"),
+            Some("2017-06-12 00:00:00 +02:00"),
+        );
+        let monday = interval(
+            Some("2017-06-12 00:00:00 +02:00"),
+            Some("2017-06-13 00:00:00 +02:00"),
+        );
+        let until_sunday_noon = interval(None, Some("2017-06-11 12:00:00 +02:00"));
+
+        // When/Then
+        assert_eq!(
+            Some(interval(
+                Some("2017-06-10 00:00:00 +02:00"),
+                Some("2017-06-12 00:00:00 +02:00")
+            )),
+            saturday.union(&sunday).unwrap()
+        );
+        assert_eq!(None, saturday.union(&monday).unwrap());
+        assert_eq!(
+            Some(interval(None, Some("2017-06-12 00:00:00 +02:00"))),
+            sunday.union(&until_sunday_noon).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_union_with_open_ended_intervals() {
+        // Given
+        let morning = interval(
+            Some("2017-06-10 10:00:00 +02:00"),
+            Some("2017-06-10 11:00:00 +02:00"),
+        );
+        let from_nine = interval(Some("2017-06-10 09:00:00 +02:00"), None);
+        let until_noon = interval(None, Some("2017-06-10 12:00:00 +02:00"));
+        let from_noon = interval(Some("2017-06-10 12:00:00 +02:00"), None);
+        let until_nine = interval(None, Some("2017-06-10 09:00:00 +02:00"));
+
+        // When/Then
+        assert_eq!(Some(from_nine.clone()), morning.union(&from_nine).unwrap());
+        assert_eq!(Some(from_nine.clone()), from_nine.union(&morning).unwrap());
+        assert_eq!(
+            Some(until_noon.clone()),
+            morning.union(&until_noon).unwrap()
+        );
+        assert_eq!(
+            Some(until_noon.clone()),
+            until_noon.union(&morning).unwrap()
+        );
+        assert_eq!(None, morning.union(&from_noon).unwrap());
+        assert_eq!(None, until_nine.union(&morning).unwrap());
+        assert_eq!(
+            Some(interval(None, None)),
+            until_noon.union(&from_nine).unwrap()
+        );
+        assert_eq!(
+            Some(interval(None, None)),
+            interval(None, None).union(&morning).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_union_with_empty_interval() {
+        // Given
+        let morning = interval(
+            Some("2017-06-10 10:00:00 +02:00"),
+            Some("2017-06-10 11:00:00 +02:00"),
+        );
+        let empty = interval(
+            Some("2017-06-10 15:00:00 +02:00"),
+            Some("2017-06-10 15:00:00 +02:00"),
+        );
+
+        // When/Then
+        assert_eq!(Some(morning.clone()), morning.union(&empty).unwrap());
+        assert_eq!(Some(morning.clone()), empty.union(&morning).unwrap());
+    }
+
+    #[test]
+    fn test_duration() {
+        assert_eq!(
+            Some(Duration::hours(6)),
+            interval(
+                Some("2017-06-07 18:00:00 +02:00"),
+                Some("2017-06-08 00:00:00 +02:00")
+            )
+            .duration()
+            .unwrap()
+        );
+        assert_eq!(
+            None,
+            interval(Some("2017-06-07 18:00:00 +02:00"), None)
+                .duration()
+                .unwrap()
+        );
+        assert!(interval(
+            Some("2017-06-07 18:00:00 +02:00"),
+            Some("2017-06-07 18:00:00 +02:00")
+        )
+        .is_empty()
+        .unwrap());
+    }
+}
@@ -0,0 +1,359 @@
+//! Structural validation of intent parsing results
+//!
+//! Results coming from the outside world, typically deserialized from JSON, are not guaranteed
+//! to be internally consistent. `IntentParserResult::validate` reports all the inconsistencies it
+//! finds instead of stopping at the first one, each violation being located by a path such as
+//! `alternatives[0].slots[1]`.
+
+use crate::entity::builtin_entity::BuiltinEntityKind;
+use crate::ontology::{IntentClassifierResult, IntentParserResult, Slot, SlotValue};
+use std::ops::Range;
+
+/// Inconsistency found in an `IntentParserResult`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind")]
+pub enum Violation {
+    /// The range of the slot is reversed or goes past the end of the input
+    #[serde(rename_all = "camelCase")]
+    InvalidSlotRange {
+        path: String,
+        range: Range<usize>,
+        input_length: usize,
+    },
+    /// The raw value of the slot differs from the part of the input covered by its range
+    #[serde(rename_all = "camelCase")]
+    RawValueMismatch {
+        path: String,
+        raw_value: String,
+        input_value: String,
+    },
+    /// A confidence score is not within [0, 1]
+    #[serde(rename_all = "camelCase")]
+    ConfidenceScoreOutOfBounds { path: String, confidence_score: f32 },
+    /// The slot refers to a builtin entity which cannot produce this kind of value
+    #[serde(rename_all = "camelCase")]
+    SlotValueMismatch {
+        path: String,
+        entity: String,
+        value_kind: String,
+    },
+    /// The alternative has a higher confidence score than the intent ranked before it
+    #[serde(rename_all = "camelCase")]
+    UnsortedAlternative {
+        path: String,
+        confidence_score: f32,
+        previous_confidence_score: f32,
+    },
+    /// The intent is not declared in the intent schemas
+    #[serde(rename_all = "camelCase")]
+    UnknownIntent { path: String, intent_name: String },
+    /// The slot is not declared in the schema of its intent
+    #[serde(rename_all = "camelCase")]
+    UnknownSlot {
+        path: String,
+        intent_name: String,
+        slot_name: String,
+    },
+    /// The slot refers to another entity than the one declared in the schema of its intent
+    #[serde(rename_all = "camelCase")]
+    EntityMismatch {
+        path: String,
+        entity: String,
+        expected_entity: String,
+    },
+}
+
+impl Violation {
+    /// Location of the violation in the result
+    pub fn path(&self) -> &str {
+        match self {
+            Violation::InvalidSlotRange { path, .. } => path,
+            Violation::RawValueMismatch { path, .. } => path,
+            Violation::ConfidenceScoreOutOfBounds { path, .. } => path,
+            Violation::SlotValueMismatch { path, .. } => path,
+            Violation::UnsortedAlternative { path, .. } => path,
+            Violation::UnknownIntent { path, .. } => path,
+            Violation::UnknownSlot { path, .. } => path,
+            Violation::EntityMismatch { path, .. } => path,
+        }
+    }
+}
+
+impl SlotValue {
+    /// Name of the variant, as found in the `kind` field of the JSON representation
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SlotValue::Custom(_) => "Custom",
+            SlotValue::Number(_) => "Number",
+            SlotValue::Ordinal(_) => "Ordinal",
+            SlotValue::Percentage(_) => "Percentage",
+            SlotValue::InstantTime(_) => "InstantTime",
+            SlotValue::TimeInterval(_) => "TimeInterval",
+            SlotValue::AmountOfMoney(_) => "AmountOfMoney",
+            SlotValue::Temperature(_) => "Temperature",
+            SlotValue::Duration(_) => "Duration",
+            SlotValue::MusicAlbum(_) => "MusicAlbum",
+            SlotValue::MusicArtist(_) => "MusicArtist",
+            SlotValue::MusicTrack(_) => "MusicTrack",
+            SlotValue::City(_) => "City",
+            SlotValue::Country(_) => "Country",
+            SlotValue::Region(_) => "Region",
+        }
+    }
+}
+
+impl BuiltinEntityKind {
+    /// Returns whether the entity can be resolved to the given slot value
+    ///
+    /// Datetime entities are not strict about the kind of value they produce, an instant time
+    /// and a time interval being both valid resolutions of "tomorrow" for instance.
+    pub fn accepts_slot_value(&self, value: &SlotValue) -> bool {
+        match *self {
+            BuiltinEntityKind::AmountOfMoney => matches!(value, SlotValue::AmountOfMoney(_)),
+            BuiltinEntityKind::Duration => matches!(value, SlotValue::Duration(_)),
+            BuiltinEntityKind::Number => matches!(value, SlotValue::Number(_)),
+            BuiltinEntityKind::Ordinal => matches!(value, SlotValue::Ordinal(_)),
+            BuiltinEntityKind::Temperature => matches!(value, SlotValue::Temperature(_)),
+            BuiltinEntityKind::Datetime | BuiltinEntityKind::Date | BuiltinEntityKind::Time => {
+                matches!(
+                    value,
+                    SlotValue::InstantTime(_) | SlotValue::TimeInterval(_)
+                )
+            }
+            BuiltinEntityKind::DatePeriod | BuiltinEntityKind::TimePeriod => {
+                matches!(value, SlotValue::TimeInterval(_))
+            }
+            BuiltinEntityKind::Percentage => matches!(value, SlotValue::Percentage(_)),
+            BuiltinEntityKind::MusicAlbum => matches!(value, SlotValue::MusicAlbum(_)),
+            BuiltinEntityKind::MusicArtist => matches!(value, SlotValue::MusicArtist(_)),
+            BuiltinEntityKind::MusicTrack => matches!(value, SlotValue::MusicTrack(_)),
+            BuiltinEntityKind::City => matches!(value, SlotValue::City(_)),
+            BuiltinEntityKind::Country => matches!(value, SlotValue::Country(_)),
+            BuiltinEntityKind::Region => matches!(value, SlotValue::Region(_)),
+        }
+    }
+}
+
+fn validate_confidence_score(path: String, confidence_score: f32, violations: &mut Vec<Violation>) {
+    if !(0.0..=1.0).contains(&confidence_score) {
+        violations.push(Violation::ConfidenceScoreOutOfBounds {
+            path,
+            confidence_score,
+        })
+    }
+}
+
+fn validate_intent(path: String, intent: &IntentClassifierResult, violations: &mut Vec<Violation>) {
+    validate_confidence_score(path, intent.confidence_score, violations)
+}
+
+fn validate_slot(path: String, slot: &Slot, input: &str, violations: &mut Vec<Violation>) {
+    let input_length = input.chars().count();
+    if slot.range.start > slot.range.end || slot.range.end > input_length {
+        violations.push(Violation::InvalidSlotRange {
+            path: path.clone(),
+            range: slot.range.clone(),
+            input_length,
+        })
+    } else {
+        let input_value = input
+            .chars()
+            .skip(slot.range.start)
+            .take(slot.range.end - slot.range.start)
+            .collect::<String>();
+        if input_value != slot.raw_value {
+            violations.push(Violation::RawValueMismatch {
+                path: path.clone(),
+                raw_value: slot.raw_value.clone(),
+                input_value,
+            })
+        }
+    }
+    if let Some(confidence_score) = slot.confidence_score {
+        validate_confidence_score(path.clone(), confidence_score, violations)
+    }
+    if let Ok(entity_kind) = BuiltinEntityKind::from_identifier(&slot.entity) {
+        for value in Some(&slot.value)
+            .into_iter()
+            .chain(slot.alternatives.iter())
+        {
+            if !entity_kind.accepts_slot_value(value) {
+                violations.push(Violation::SlotValueMismatch {
+                    path: path.clone(),
+                    entity: slot.entity.clone(),
+                    value_kind: value.kind().to_string(),
+                })
+            }
+        }
+    }
+}
+
+fn validate_slots(path: &str, slots: &[Slot], input: &str, violations: &mut Vec<Violation>) {
+    for (index, slot) in slots.iter().enumerate() {
+        validate_slot(format!("{}[{}]", path, index), slot, input, violations)
+    }
+}
+
+impl IntentParserResult {
+    /// Checks the internal consistency of the result and returns all the violations found
+    ///
+    /// Slot ranges are expected to be expressed in Unicode scalar values, and alternatives to be
+    /// sorted by decreasing confidence score, none of them scoring higher than the top intent.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        validate_intent("intent".to_string(), &self.intent, &mut violations);
+        validate_slots("slots", &self.slots, &self.input, &mut violations);
+        let mut previous_confidence_score = self.intent.confidence_score;
+        for (index, alternative) in self.alternatives.iter().enumerate() {
+            let path = format!("alternatives[{}]", index);
+            validate_intent(
+                format!("{}.intent", path),
+                &alternative.intent,
+                &mut violations,
+            );
+            validate_slots(
+                &format!("{}.slots", path),
+                &alternative.slots,
+                &self.input,
+                &mut violations,
+            );
+            let confidence_score = alternative.intent.confidence_score;
+            if confidence_score > previous_confidence_score {
+                violations.push(Violation::UnsortedAlternative {
+                    path,
+                    confidence_score,
+                    previous_confidence_score,
+                })
+            }
+            previous_confidence_score = confidence_score;
+        }
+        violations
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::{IntentParserAlternative, NumberValue};
+
+    fn intent(intent_name: Option<&str>, confidence_score: f32) -> IntentClassifierResult {
+        IntentClassifierResult {
+            intent_name: intent_name.map(|name| name.to_string()),
+            confidence_score,
+        }
+    }
+
+    fn number_slot(raw_value: &str, range: Range<usize>, entity: &str) -> Slot {
+        Slot {
+            raw_value: raw_value.to_string(),
+            value: SlotValue::Number(NumberValue { value: 3. }),
+            alternatives: vec![],
+            range,
+            entity: entity.to_string(),
+            slot_name: "count".to_string(),
+            confidence_score: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_result() {
+        // Given
+        let result = IntentParserResult {
+            input: "réserve 3 tables".to_string(),
+            intent: intent(Some("bookRestaurant"), 0.8),
+            slots: vec![number_slot("3", 8..9, "snips/number")],
+            alternatives: vec![
+                IntentParserAlternative {
+                    intent: intent(None, 0.15),
+                    slots: vec![],
+                },
+                IntentParserAlternative {
+                    intent: intent(Some("bookTable"), 0.05),
+                    slots: vec![number_slot("3", 8..9, "count")],
+                },
+            ],
+        };
+
+        // When
+        let violations = result.validate();
+
+        // Then
+        assert_eq!(Vec::<Violation>::new(), violations);
+        assert!(result.is_valid());
+    }
+
+    #[test]
+    fn test_all_violations_are_reported() {
+        // Given
+        let mut out_of_bounds_slot = number_slot("3", 8..20, "snips/number");
+        out_of_bounds_slot.confidence_score = Some(1.5);
+        let result = IntentParserResult {
+            input: "réserve 3 tables".to_string(),
+            intent: intent(Some("bookRestaurant"), 0.4),
+            slots: vec![
+                number_slot("4", 8..9, "snips/number"),
+                number_slot("3", 8..9, "snips/datetime"),
+            ],
+            alternatives: vec![IntentParserAlternative {
+                intent: intent(None, 0.6),
+                slots: vec![out_of_bounds_slot],
+            }],
+        };
+
+        // When
+        let violations = result.validate();
+
+        // Then
+        let expected_violations = vec![
+            Violation::RawValueMismatch {
+                path: "slots[0]".to_string(),
+                raw_value: "4".to_string(),
+                input_value: "3".to_string(),
+            },
+            Violation::SlotValueMismatch {
+                path: "slots[1]".to_string(),
+                entity: "snips/datetime".to_string(),
+                value_kind: "Number".to_string(),
+            },
+            Violation::InvalidSlotRange {
+                path: "alternatives[0].slots[0]".to_string(),
+                range: 8..20,
+                input_length: 16,
+            },
+            Violation::ConfidenceScoreOutOfBounds {
+                path: "alternatives[0].slots[0]".to_string(),
+                confidence_score: 1.5,
+            },
+            Violation::UnsortedAlternative {
+                path: "alternatives[0]".to_string(),
+                confidence_score: 0.6,
+                previous_confidence_score: 0.4,
+            },
+        ];
+        assert_eq!(expected_violations, violations);
+    }
+
+    #[test]
+    fn test_violation_serialization() {
+        // Given
+        let violation = Violation::InvalidSlotRange {
+            path: "slots[0]".to_string(),
+            range: 8..20,
+            input_length: 16,
+        };
+
+        // When
+        let serialized = serde_json::to_string(&violation).unwrap();
+
+        // Then
+        assert_eq!(
+            r#"{"kind":"InvalidSlotRange","path":"slots[0]","range":{"start":8,"end":20},"inputLength":16}"#,
+            serialized
+        );
+    }
+}